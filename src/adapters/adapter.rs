@@ -1,18 +1,30 @@
 use super::{aqua_constant, aqua_stable, comet, phoenix, soroswap};
 use crate::storage;
-use crate::types::{error::BrokerError, protocol::Protocol, swapinfo::LPSwap};
+use crate::types::{
+    error::BrokerError, poolreserves::PoolReserves, protocol::Protocol, step::PathStep,
+    swapinfo::LPSwap,
+};
 use soroban_sdk::{panic_with_error, Env};
 
 // Standard interface for all LP protocol adapters
 pub trait AdapterTrait {
     // Executes the swap directly through LP contract
     fn swap(&self, env: &Env, swap: LPSwap) -> i128;
+    // Executes the swap through LP contract with try-invocation, returns None if the swap failed or yields less than min
+    //
+    // Soroswap pairs receive tokens before the swap call, so the Soroswap adapter only checks reserves and
+    // the projected amount in advance, a panic inside the pair still reverts the whole transaction
+    fn try_swap(&self, env: &Env, swap: LPSwap, min: i128) -> Option<i128>;
+    // Retrieves reserves of constant product pools, returns None for other pool types or if reserves are unavailable
+    fn get_reserves(&self, _env: &Env, _step: &PathStep) -> Option<PoolReserves> {
+        None
+    }
 }
 
 // Resolve contract adapter for a given protocol
 pub fn swap_adapter(e: &Env, protocol: Protocol, si: LPSwap) -> i128 {
     //protocol should be enabled
-    if !storage::is_protocol_enabled(&e, &protocol) {
+    if !storage::is_protocol_enabled(e, &protocol) {
         panic_with_error!(&e, BrokerError::ProtocolDisabled);
     }
     //match by protocol
@@ -24,3 +36,50 @@ pub fn swap_adapter(e: &Env, protocol: Protocol, si: LPSwap) -> i128 {
         Protocol::Phoenix => phoenix::PhoenixAdapter.swap(e, si),
     }
 }
+
+// Resolve contract adapter for a given protocol and try to execute the swap, returns None on failure
+// or if bought amount is less than min
+pub fn try_swap_adapter(e: &Env, protocol: Protocol, si: LPSwap, min: i128) -> Option<i128> {
+    //disabled protocol fails the swap
+    if !storage::is_protocol_enabled(e, &protocol) {
+        return None;
+    }
+    //match by protocol
+    match protocol {
+        Protocol::AquaConstant => aqua_constant::AquaConstantAdapter.try_swap(e, si, min),
+        Protocol::AquaStable => aqua_stable::AquaStableAdapter.try_swap(e, si, min),
+        Protocol::Soroswap => soroswap::SoroswapAdapter.try_swap(e, si, min),
+        Protocol::Comet => comet::CometAdapter.try_swap(e, si, min),
+        Protocol::Phoenix => phoenix::PhoenixAdapter.try_swap(e, si, min),
+    }
+}
+
+// Resolve contract adapter for a given path step and retrieve pool reserves, returns None if the pool
+// is not a constant product pool
+pub fn get_adapter_reserves(e: &Env, step: &PathStep) -> Option<PoolReserves> {
+    //disabled protocol can't be used for the swap
+    if !storage::is_protocol_enabled(e, &step.protocol) {
+        return None;
+    }
+    //match by protocol
+    match step.protocol {
+        Protocol::AquaConstant => aqua_constant::AquaConstantAdapter.get_reserves(e, step),
+        Protocol::AquaStable => aqua_stable::AquaStableAdapter.get_reserves(e, step),
+        Protocol::Soroswap => soroswap::SoroswapAdapter.get_reserves(e, step),
+        Protocol::Comet => comet::CometAdapter.get_reserves(e, step),
+        Protocol::Phoenix => phoenix::PhoenixAdapter.get_reserves(e, step),
+    }
+}
+
+// Estimate bought amount based on the constant product pool reserves
+pub fn estimate_amount_out(reserves: &PoolReserves, amount_in: i128) -> i128 {
+    match reserves.protocol {
+        Protocol::Soroswap => soroswap::calc_soroswap_amount_out(
+            amount_in,
+            &(reserves.reserve_in, reserves.reserve_out),
+            false,
+        ),
+        //only Aqua constant product pools provide reserves besides Soroswap
+        _ => aqua_constant::calc_aqua_amount_out(amount_in, reserves),
+    }
+}
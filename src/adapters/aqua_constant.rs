@@ -1,8 +1,8 @@
-use soroban_sdk::{contract, contractclient, Address, Env};
+use soroban_sdk::{contract, contractclient, Address, Env, Vec};
 
 use super::adapter::AdapterTrait;
 use crate::auth::add_transfer_auth;
-use crate::types::swapinfo::LPSwap;
+use crate::types::{poolreserves::PoolReserves, step::PathStep, swapinfo::LPSwap};
 
 #[contractclient(name = "AquaPoolClient")]
 #[allow(dead_code)]
@@ -15,6 +15,8 @@ pub trait AquaPoolTrait {
         in_amount: u128,
         out_min: u128,
     ) -> u128;
+    fn get_reserves(e: Env) -> Vec<u128>;
+    fn get_fee_fraction(e: Env) -> u32;
 }
 
 #[contract]
@@ -22,7 +24,7 @@ pub struct AquaConstantAdapter;
 
 impl AdapterTrait for AquaConstantAdapter {
     fn swap(&self, env: &Env, si: LPSwap) -> i128 {
-        let client = AquaPoolClient::new(&env, &si.step.pool);
+        let client = AquaPoolClient::new(env, &si.step.pool);
 
         if si.to == env.current_contract_address() {
             add_transfer_auth(env, &si.step.pool, &si.in_token, si.amount);
@@ -32,4 +34,54 @@ impl AdapterTrait for AquaConstantAdapter {
 
         client.swap(&si.to, &si.step.si, &si.step.bi, &selling, &1u128) as i128
     }
+
+    fn try_swap(&self, env: &Env, si: LPSwap, min: i128) -> Option<i128> {
+        let client = AquaPoolClient::new(env, &si.step.pool);
+
+        if si.to == env.current_contract_address() {
+            add_transfer_auth(env, &si.step.pool, &si.in_token, si.amount);
+        }
+
+        let selling = si.amount as u128;
+
+        match client.try_swap(&si.to, &si.step.si, &si.step.bi, &selling, &(min as u128)) {
+            Ok(Ok(bought)) => Some(bought as i128),
+            _ => None,
+        }
+    }
+
+    fn get_reserves(&self, env: &Env, step: &PathStep) -> Option<PoolReserves> {
+        let client = AquaPoolClient::new(env, &step.pool);
+        let reserves = match client.try_get_reserves() {
+            Ok(Ok(reserves)) => reserves,
+            _ => return None,
+        };
+        let fee = match client.try_get_fee_fraction() {
+            Ok(Ok(fee)) => fee,
+            _ => return None,
+        };
+        Some(PoolReserves {
+            protocol: step.protocol,
+            reserve_in: reserves.get(step.si)? as i128,
+            reserve_out: reserves.get(step.bi)? as i128,
+            fee,
+        })
+    }
+}
+
+const AQUA_FEEM: i128 = 10_000;
+
+// Estimate amount_out for Aqua constant product LPs
+pub fn calc_aqua_amount_out(amount_in: i128, reserves: &PoolReserves) -> i128 {
+    let numerator = amount_in.checked_mul(reserves.reserve_out).unwrap();
+    let denominator = reserves.reserve_in.checked_add(amount_in).unwrap();
+    let result = numerator.checked_div(denominator).unwrap();
+    //fee is rounded up
+    let fee_numerator = result.checked_mul(reserves.fee as i128).unwrap();
+    let fee = fee_numerator
+        .checked_add(AQUA_FEEM - 1)
+        .unwrap()
+        .checked_div(AQUA_FEEM)
+        .unwrap();
+    result.checked_sub(fee).unwrap()
 }
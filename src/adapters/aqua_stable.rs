@@ -22,12 +22,25 @@ pub struct AquaStableAdapter;
 
 impl AdapterTrait for AquaStableAdapter {
     fn swap(&self, env: &Env, si: LPSwap) -> i128 {
-        let client = AquaStablePoolClient::new(&env, &si.step.pool);
+        let client = AquaStablePoolClient::new(env, &si.step.pool);
 
         if si.to == env.current_contract_address() {
             add_transfer_auth(env, &si.step.pool, &si.in_token, si.amount);
         }
         let selling = &(si.amount as u128);
-        client.swap(&si.to, &si.step.si, &si.step.bi, &selling, &1u128) as i128
+        client.swap(&si.to, &si.step.si, &si.step.bi, selling, &1u128) as i128
+    }
+
+    fn try_swap(&self, env: &Env, si: LPSwap, min: i128) -> Option<i128> {
+        let client = AquaStablePoolClient::new(env, &si.step.pool);
+
+        if si.to == env.current_contract_address() {
+            add_transfer_auth(env, &si.step.pool, &si.in_token, si.amount);
+        }
+        let selling = &(si.amount as u128);
+        match client.try_swap(&si.to, &si.step.si, &si.step.bi, selling, &(min as u128)) {
+            Ok(Ok(bought)) => Some(bought as i128),
+            _ => None,
+        }
     }
 }
@@ -22,7 +22,7 @@ pub struct CometAdapter;
 
 impl AdapterTrait for CometAdapter {
     fn swap(&self, env: &Env, si: LPSwap) -> i128 {
-        let client = CometPoolClient::new(&env, &si.step.pool);
+        let client = CometPoolClient::new(env, &si.step.pool);
 
         if si.to == env.current_contract_address() {
             add_approve_auth(env, &si.step.pool, &si.in_token, si.amount);
@@ -39,6 +39,26 @@ impl AdapterTrait for CometAdapter {
             )
             .0
     }
+
+    fn try_swap(&self, env: &Env, si: LPSwap, min: i128) -> Option<i128> {
+        let client = CometPoolClient::new(env, &si.step.pool);
+
+        if si.to == env.current_contract_address() {
+            add_approve_auth(env, &si.step.pool, &si.in_token, si.amount);
+        }
+
+        match client.try_swap_exact_amount_in(
+            &si.in_token,
+            &si.amount,
+            &si.step.asset,
+            &min,
+            &MAX_PRICE,
+            &si.to,
+        ) {
+            Ok(Ok((bought, _))) => Some(bought),
+            _ => None,
+        }
+    }
 }
 
 const MAX_PRICE: i128 = 18_446_744_073_709_551_615;
@@ -3,4 +3,6 @@ pub mod aqua_constant;
 pub mod aqua_stable;
 pub mod soroswap;
 pub mod comet;
+//generated Phoenix pool client mirrors the pool swap signature
+#[allow(clippy::too_many_arguments)]
 pub mod phoenix;
\ No newline at end of file
@@ -23,12 +23,33 @@ pub struct PhoenixAdapter;
 
 impl AdapterTrait for PhoenixAdapter {
     fn swap(&self, env: &Env, si: LPSwap) -> i128 {
-        let client = PhoenixPoolClient::new(&env, &si.step.pool);
+        let client = PhoenixPoolClient::new(env, &si.step.pool);
 
-        if &si.to == &env.current_contract_address() {
+        if si.to == env.current_contract_address() {
             add_transfer_auth(env, &si.step.pool, &si.in_token, si.amount);
         }
 
         client.swap(&si.to, &si.in_token, &si.amount, &None, &None, &None, &None)
     }
+
+    fn try_swap(&self, env: &Env, si: LPSwap, min: i128) -> Option<i128> {
+        let client = PhoenixPoolClient::new(env, &si.step.pool);
+
+        if si.to == env.current_contract_address() {
+            add_transfer_auth(env, &si.step.pool, &si.in_token, si.amount);
+        }
+
+        match client.try_swap(
+            &si.to,
+            &si.in_token,
+            &si.amount,
+            &Some(min),
+            &None,
+            &None,
+            &None,
+        ) {
+            Ok(Ok(bought)) => Some(bought),
+            _ => None,
+        }
+    }
 }
@@ -1,7 +1,9 @@
 use soroban_sdk::{contract, contractclient, panic_with_error, token, Address, Env, Error};
 
 use super::adapter::AdapterTrait;
-use crate::types::{error::BrokerError, swapinfo::LPSwap};
+use crate::types::{
+    error::BrokerError, poolreserves::PoolReserves, step::PathStep, swapinfo::LPSwap,
+};
 
 #[contractclient(name = "SoroswapClient")]
 #[allow(dead_code)]
@@ -33,6 +35,42 @@ impl AdapterTrait for SoroswapAdapter {
 
         amount_out
     }
+
+    fn try_swap(&self, e: &Env, si: LPSwap, min: i128) -> Option<i128> {
+        let swap_client = SoroswapClient::new(e, &si.step.pool);
+        let reserves = match swap_client.try_get_reserves() {
+            Ok(Ok(reserves)) => reserves,
+            _ => return None,
+        };
+        if si.step.bi > 1 {
+            return None;
+        }
+        let amount_out = calc_soroswap_amount_out(si.amount, &reserves, si.step.bi == 0);
+        if amount_out < min {
+            return None;
+        }
+        //tokens are sent to the pair before the swap, so it can't be skipped after the transfer,
+        //a failure inside the pair reverts the whole transaction
+        Some(self.swap(e, si))
+    }
+
+    fn get_reserves(&self, e: &Env, step: &PathStep) -> Option<PoolReserves> {
+        let (reserve_0, reserve_1) = match SoroswapClient::new(e, &step.pool).try_get_reserves() {
+            Ok(Ok(reserves)) => reserves,
+            _ => return None,
+        };
+        let (reserve_in, reserve_out) = match step.bi {
+            0 => (reserve_1, reserve_0),
+            1 => (reserve_0, reserve_1),
+            _ => return None,
+        };
+        Some(PoolReserves {
+            protocol: step.protocol,
+            reserve_in,
+            reserve_out,
+            fee: SOROSWAP_FEE as u32,
+        })
+    }
 }
 
 const SOROSWAP_FEE: i128 = 30;
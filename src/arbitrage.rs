@@ -0,0 +1,61 @@
+use soroban_sdk::{panic_with_error, token, Address, Env, Vec};
+
+use crate::types::{error::BrokerError, route::Route};
+use crate::{
+    calc_share, estimate_routes, get_balances, get_buying_asset, get_interim_tokens,
+    perform_route_swap, storage, verify_balances,
+};
+
+// Execute cyclic routes selling and buying the same token, and split the profit between
+// the caller and the treasury (contract balance)
+pub fn arbitrage(
+    e: &Env,
+    token: &Address,
+    routes: &Vec<Route>,
+    caller: &Address,
+    min_profit: i128,
+) -> Vec<i128> {
+    if min_profit < 0 {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    if &get_buying_asset(e, routes) != token {
+        panic_with_error!(e, BrokerError::InvalidPath);
+    }
+    let (amount, _) = estimate_routes(routes);
+    let broker = e.current_contract_address();
+    let token_client = token::Client::new(e, token);
+    token_client.transfer(caller, &broker, &amount);
+
+    //make balances snapshot before swap
+    let balance_before = token_client.balance(&broker);
+    let interim_tokens = get_interim_tokens(
+        e,
+        routes,
+        &Vec::new(e),
+        &Vec::from_array(e, [token.clone()]),
+    );
+    let interim_balances_before = get_balances(e, &interim_tokens);
+    //execute cyclic routes
+    let mut bought: i128 = 0;
+    for route in routes.iter() {
+        let (swap_result, _) = perform_route_swap(e, &route, token, &broker);
+        bought = bought.checked_add(swap_result).unwrap();
+    }
+    //net profit is based on the actual balance change
+    let profit = token_client
+        .balance(&broker)
+        .checked_sub(balance_before)
+        .unwrap();
+    if bought.checked_sub(amount).unwrap() != profit {
+        panic_with_error!(e, BrokerError::Misconduct);
+    }
+    if profit <= 0 || profit < min_profit {
+        panic_with_error!(e, BrokerError::Unfeasible);
+    }
+    verify_balances(e, &interim_tokens, &interim_balances_before);
+    //treasury share stays on the contract balance
+    let treasury_profit = calc_share(profit, storage::get_arbitrage_share(e));
+    let caller_profit = profit.checked_sub(treasury_profit).unwrap();
+    token_client.transfer(&broker, caller, &amount.checked_add(caller_profit).unwrap());
+    Vec::from_array(e, [amount, bought, caller_profit, treasury_profit])
+}
@@ -1,15 +1,60 @@
 use crate::{storage, types};
-use soroban_sdk::{auth, panic_with_error, symbol_short, Address, Env, IntoVal, Vec};
+use soroban_sdk::{
+    auth, panic_with_error, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Vec,
+};
+use types::{error::BrokerError, swaprequest::SwapRequest};
 
 // Panic if current user is not admin
 pub fn require_admin(e: &Env) {
-    let admin = storage::get_admin(&e);
+    let admin = storage::get_admin(e);
     if admin.is_none() {
-        panic_with_error!(e, types::error::BrokerError::Unauthorized);
+        panic_with_error!(e, BrokerError::Unauthorized);
     }
     admin.unwrap().require_auth()
 }
 
+// Panic if the admin requires all quotes to be signed by the router service
+pub fn require_unsigned_quotes_allowed(e: &Env) {
+    if storage::is_signed_quote_required(e) {
+        panic_with_error!(e, BrokerError::SignedQuoteRequired);
+    }
+}
+
+// Verify that the swap request was signed by the router service and consume quote nonce
+pub fn require_signed_quote(
+    e: &Env,
+    request: &SwapRequest,
+    nonce: u64,
+    expiration: u32,
+    signature: &BytesN<64>,
+) {
+    let signer = storage::get_quote_signer(e);
+    if signer.is_none() {
+        panic_with_error!(e, BrokerError::Unauthorized);
+    }
+    //quote should be valid at the current ledger and nonce should outlive it
+    let ledger = e.ledger().sequence();
+    if expiration < ledger {
+        panic_with_error!(e, BrokerError::QuoteExpired);
+    }
+    if expiration - ledger > e.storage().max_ttl() {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    //signed payload is bound to the current contract address
+    let payload = (
+        e.current_contract_address(),
+        request.clone(),
+        nonce,
+        expiration,
+    )
+        .to_xdr(e);
+    let hash = e.crypto().sha256(&payload);
+    e.crypto()
+        .ed25519_verify(&signer.unwrap(), &Bytes::from(hash), signature);
+    //prevent quote replay
+    storage::use_nonce(e, &request.trader, nonce, expiration);
+}
+
 // Add authorization for the current contract to call the transfer function of the selling token
 pub fn add_transfer_auth(env: &Env, pool: &Address, token: &Address, amount: i128) {
     let invocation = auth::InvokerContractAuthEntry::Contract(auth::SubContractInvocation {
@@ -17,7 +62,7 @@ pub fn add_transfer_auth(env: &Env, pool: &Address, token: &Address, amount: i12
             contract: token.clone(),
             fn_name: symbol_short!("transfer"),
             args: Vec::from_array(
-                &env,
+                env,
                 [
                     env.current_contract_address().to_val(),
                     pool.clone().to_val(),
@@ -25,7 +70,7 @@ pub fn add_transfer_auth(env: &Env, pool: &Address, token: &Address, amount: i12
                 ],
             ),
         },
-        sub_invocations: Vec::new(&env),
+        sub_invocations: Vec::new(env),
     });
 
     env.authorize_as_current_contract(Vec::from_array(env, [invocation]));
@@ -39,7 +84,7 @@ pub fn add_approve_auth(env: &Env, pool: &Address, token: &Address, max_amount:
                 contract: token.clone(),
                 fn_name: symbol_short!("approve"),
                 args: Vec::from_array(
-                    &env,
+                    env,
                     [
                         env.current_contract_address().to_val(),
                         pool.clone().to_val(),
@@ -48,7 +93,7 @@ pub fn add_approve_auth(env: &Env, pool: &Address, token: &Address, max_amount:
                     ],
                 ),
             },
-            sub_invocations: Vec::new(&env),
+            sub_invocations: Vec::new(env),
         });
 
     env.authorize_as_current_contract(Vec::from_array(env, [approve_invocation]));
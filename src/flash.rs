@@ -0,0 +1,69 @@
+use soroban_sdk::{contractclient, panic_with_error, token, Address, Bytes, Env, Vec};
+
+use crate::types::{error::BrokerError, outcome::Funding, swaprequest::SwapRequest};
+use crate::{calc_share, estimate_routes, execute_swap, pay_out, storage};
+
+// Standard callback interface of flash swap receivers
+#[contractclient(name = "FlashReceiverClient")]
+#[allow(dead_code)]
+pub trait FlashReceiverTrait {
+    // Invoked after bought tokens were transferred to the receiver,
+    // the receiver should transfer the selling amount to the broker before returning
+    fn on_flash_swap(
+        initiator: Address,
+        selling: Address,
+        amount: i128,
+        buying: Address,
+        bought: i128,
+        data: Bytes,
+    );
+}
+
+// Execute swap with the contract balance, transfer bought tokens to the receiver, invoke the receiver
+// callback, and verify that the selling amount and the flash fee were delivered back
+pub fn flash_swap(e: &Env, request: &SwapRequest, receiver: &Address, data: &Bytes) -> Vec<i128> {
+    let config = match storage::get_flash_config(e) {
+        Some(config) if config.enabled => config,
+        _ => panic_with_error!(e, BrokerError::FlashDisabled),
+    };
+    let broker = e.current_contract_address();
+    let selling_token_client = token::Client::new(e, &request.selling);
+    let (amount, _) = estimate_routes(&request.routes);
+    //swap is funded from the capped share of the unreserved contract balance
+    let available = selling_token_client
+        .balance(&broker)
+        .checked_sub(storage::get_reserved(e, &request.selling))
+        .unwrap();
+    if amount > calc_share(available, config.max_share) {
+        panic_with_error!(e, BrokerError::ReservedFunds);
+    }
+    let outcome = execute_swap(e, request, Funding::Escrow, None, false, None);
+    pay_out(e, &outcome, receiver);
+    //balance snapshot after the swap (the converted fee may be credited in selling tokens)
+    let balance_before = selling_token_client.balance(&broker);
+    FlashReceiverClient::new(e, receiver).on_flash_swap(
+        &request.trader,
+        &request.selling,
+        &outcome.sold,
+        &outcome.buying,
+        &outcome.bought,
+        data,
+    );
+    //receiver should deliver at least the sold amount plus the flash fee (rounded up)
+    let flash_fee = outcome
+        .sold
+        .checked_mul(config.fee as i128)
+        .unwrap()
+        .checked_add(9_999)
+        .unwrap()
+        .checked_div(10_000)
+        .unwrap();
+    let delivered = selling_token_client
+        .balance(&broker)
+        .checked_sub(balance_before)
+        .unwrap();
+    if delivered < outcome.sold.checked_add(flash_fee).unwrap() {
+        panic_with_error!(e, BrokerError::FlashNotRepaid);
+    }
+    outcome.to_vec(e)
+}
@@ -0,0 +1,149 @@
+use soroban_sdk::{panic_with_error, Address, Env, Map, Vec};
+
+use crate::types::{
+    error::BrokerError, graphstep::GraphStep, protocol::Protocol, route::Route, swapinfo::LPSwap,
+};
+use crate::{calc_fee, storage, swap_adapter};
+
+// Total node share (in basis points)
+const FULL_SHARE: u32 = 10_000;
+// Portion of the route volume passing through a node or step is scaled by this factor
+const FULL_FLOW: i128 = 1_000_000_000;
+
+// Execute route graph, returns bought amount and protocols fixed fee amount
+//
+// Each protocol fee is charged only from the portion of the route volume routed through the protocol,
+// steps should be ordered topologically and node shares should add up to 100%
+pub fn perform_graph_swap(e: &Env, route: &Route, selling: &Address, to: &Address) -> (i128, i128) {
+    if !route.path.is_empty() || !route.alternatives.is_empty() {
+        panic_with_error!(e, BrokerError::InvalidPath);
+    }
+    let output_node = get_output_node(e, &route.graph);
+    //node tokens, accumulated amounts, and spent amounts and shares
+    let mut tokens: Map<u32, Address> = Map::new(e);
+    let mut amounts: Map<u32, i128> = Map::new(e);
+    let mut spent: Map<u32, (i128, u32)> = Map::new(e);
+    tokens.set(0, selling.clone());
+    amounts.set(0, route.amount);
+    //portions of the route volume passing through nodes and protocols
+    let mut flows: Map<u32, i128> = Map::new(e);
+    let mut protocol_flows: Map<Protocol, i128> = Map::new(e);
+    flows.set(0, FULL_FLOW);
+    for graph_step in route.graph.iter() {
+        let from = graph_step.from;
+        let to_node = graph_step.to;
+        //steps should be ordered topologically and never leave the output node
+        if from >= to_node || from == output_node || graph_step.share == 0 {
+            panic_with_error!(e, BrokerError::InvalidPath);
+        }
+        //node amount can't be split before all incoming steps are executed
+        if spent.contains_key(to_node) {
+            panic_with_error!(e, BrokerError::InvalidPath);
+        }
+        let in_token = tokens
+            .get(from)
+            .unwrap_or_else(|| panic_with_error!(e, BrokerError::InvalidPath));
+        let node_amount = amounts.get(from).unwrap();
+        //calculate the step amount, the last split receives the remainder to avoid rounding dust
+        let (spent_amount, spent_share) = spent.get(from).unwrap_or((0, 0));
+        let share = spent_share.checked_add(graph_step.share).unwrap();
+        let amount = match share {
+            FULL_SHARE => node_amount.checked_sub(spent_amount).unwrap(),
+            s if s < FULL_SHARE => node_amount
+                .checked_mul(graph_step.share as i128)
+                .unwrap()
+                .checked_div(FULL_SHARE as i128)
+                .unwrap(),
+            _ => panic_with_error!(e, BrokerError::InvalidPath),
+        };
+        spent.set(from, (spent_amount.checked_add(amount).unwrap(), share));
+        //track the route volume portion routed through the step
+        let flow = flows
+            .get(from)
+            .unwrap()
+            .checked_mul(graph_step.share as i128)
+            .unwrap()
+            .checked_div(FULL_SHARE as i128)
+            .unwrap();
+        let node_flow = flows.get(to_node).unwrap_or_default();
+        flows.set(to_node, node_flow.checked_add(flow).unwrap());
+        let protocol_flow = protocol_flows
+            .get(graph_step.step.protocol)
+            .unwrap_or_default();
+        protocol_flows.set(
+            graph_step.step.protocol,
+            protocol_flow.checked_add(flow).unwrap(),
+        );
+        //merged steps should deliver the same token
+        let buying = graph_step.step.asset.clone();
+        match tokens.get(to_node) {
+            Some(token) if token != buying => panic_with_error!(e, BrokerError::InvalidPath),
+            Some(_) => {}
+            None => tokens.set(to_node, buying),
+        }
+        //execute the swap and accumulate the bought amount in the destination node
+        let bought = if amount > 0 {
+            let protocol = graph_step.step.protocol;
+            swap_adapter(
+                e,
+                protocol,
+                LPSwap {
+                    step: graph_step.step,
+                    in_token,
+                    amount,
+                    to: to.clone(),
+                },
+            )
+        } else {
+            0
+        };
+        let accumulated = amounts.get(to_node).unwrap_or_default();
+        amounts.set(to_node, accumulated.checked_add(bought).unwrap());
+    }
+    //every intermediate node should be fully spent
+    for node in tokens.keys().iter() {
+        if node != output_node && spent.get(node).map(|(_, share)| share) != Some(FULL_SHARE) {
+            panic_with_error!(e, BrokerError::InvalidPath);
+        }
+    }
+    let bought = amounts.get(output_node).unwrap();
+    (bought, get_graph_protocol_fee(e, &protocol_flows, bought))
+}
+
+// Sum protocol fees charged from the bought amount portions routed through each protocol
+fn get_graph_protocol_fee(e: &Env, protocol_flows: &Map<Protocol, i128>, bought: i128) -> i128 {
+    let mut fee: i128 = 0;
+    for (protocol, flow) in protocol_flows.iter() {
+        //volume passing through several steps of the same protocol is charged only once
+        let volume = bought
+            .checked_mul(flow.min(FULL_FLOW))
+            .unwrap()
+            .checked_div(FULL_FLOW)
+            .unwrap();
+        fee = fee
+            .checked_add(calc_fee(volume, storage::get_protocol_fee(e, &protocol)))
+            .unwrap();
+    }
+    fee
+}
+
+// Retrieve buying token address of the route graph
+pub fn get_graph_buying_asset(e: &Env, graph: &Vec<GraphStep>) -> Option<Address> {
+    if graph.is_empty() {
+        return None;
+    }
+    let output_node = get_output_node(e, graph);
+    graph
+        .iter()
+        .find(|graph_step| graph_step.to == output_node)
+        .map(|graph_step| graph_step.step.asset)
+}
+
+// Find the output node (node with the highest index)
+fn get_output_node(e: &Env, graph: &Vec<GraphStep>) -> u32 {
+    graph
+        .iter()
+        .map(|graph_step| graph_step.to)
+        .max()
+        .unwrap_or_else(|| panic_with_error!(e, BrokerError::InvalidPath))
+}
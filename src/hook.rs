@@ -0,0 +1,23 @@
+use soroban_sdk::{contractclient, Address, Bytes, Env};
+
+use crate::pay_out;
+use crate::types::{outcome::SwapOutcome, swaphook::SwapHook};
+
+// Standard interface of post-swap hook contracts
+#[contractclient(name = "SwapHookClient")]
+#[allow(dead_code)]
+pub trait SwapHookTrait {
+    // Invoked after bought tokens were transferred to the hook contract on behalf of the trader
+    fn on_swap(trader: Address, token: Address, amount: i128, payload: Bytes);
+}
+
+// Transfer bought tokens to the hook contract and notify it
+pub fn pay_out_to_hook(e: &Env, outcome: &SwapOutcome, trader: &Address, hook: &SwapHook) {
+    pay_out(e, outcome, &hook.contract);
+    SwapHookClient::new(e, &hook.contract).on_swap(
+        trader,
+        &outcome.buying,
+        &outcome.bought,
+        &hook.payload,
+    );
+}
@@ -1,14 +1,50 @@
 #![no_std]
+//argument helpers generated by contractimpl don't inherit function attributes,
+//wide entry points are marked with allow(clippy::too_many_arguments) individually
+#![allow(clippy::too_many_arguments)]
 
 mod adapters;
+mod arbitrage;
 mod auth;
+mod flash;
+mod graph;
+mod hook;
+mod optimizer;
+mod oracle;
+mod orders;
+mod pathfinder;
+mod payment;
+mod rebalance;
 mod storage;
 mod tests;
 mod types;
 
-use adapters::adapter::swap_adapter;
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, BytesN, Env, Vec};
-use types::{error, protocol, route::Route, step::PathStep, swapinfo::LPSwap};
+use adapters::adapter::{swap_adapter, try_swap_adapter};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, token, Address, Bytes, BytesN, Env, IntoVal, Map, Vec,
+};
+use types::{
+    dcaorder::{DcaOrder, DcaStatus},
+    error,
+    feetier::FeeTier,
+    flashconfig::FlashConfig,
+    oracle::Asset,
+    order::LimitOrder,
+    outcome::{Funding, SwapOutcome},
+    payoutshare::PayoutShare,
+    protocol,
+    rebalancetarget::RebalanceTarget,
+    registeredpool::RegisteredPool,
+    relayerfee::RelayerFee,
+    route::Route,
+    step::PathStep,
+    swaphook::SwapHook,
+    swapinfo::LPSwap,
+    swapinput::SwapInput,
+    swapoutput::SwapOutput,
+    swaprequest::SwapRequest,
+    triggerorder::TriggerOrder,
+};
 
 #[contract]
 pub struct StellarBroker;
@@ -49,178 +85,1838 @@ impl StellarBroker {
         storage::set_protocol_enabled(&e, &protocol, enabled);
     }
 
+    // Set fixed fee charged for routing through specific LP protocol
+    //
+    // # Arguments
+    //
+    // * `protocol` - LP protocol
+    // * `fee` - Fixed fee charged from the volume routed through the protocol (in ‰)
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if the fee exceeds 100%
+    pub fn set_protocol_fee(e: Env, protocol: protocol::Protocol, fee: u32) {
+        auth::require_admin(&e);
+        if fee > 1000 {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        storage::set_protocol_fee(&e, &protocol, fee);
+    }
+
+    // Enable/disable per-protocol fixed fees instead of the caller-supplied fixed fee
+    //
+    // # Arguments
+    //
+    // * `enabled` - Whether per-protocol fixed fees should be applied
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn enable_protocol_fees(e: Env, enabled: bool) {
+        auth::require_admin(&e);
+        storage::set_protocol_fees_enabled(&e, enabled);
+    }
+
     // Update the contract's WASM hash
     //
     // # Arguments
     //
-    // * `wasm_hash` - New WASM hash
+    // * `wasm_hash` - New WASM hash
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn update_contract(e: Env, wasm_hash: BytesN<32>) {
+        auth::require_admin(&e);
+        e.deployer().update_current_contract_wasm(wasm_hash)
+    }
+
+    // Register partner or update its fee share
+    //
+    // # Arguments
+    //
+    // * `partner` - Partner address
+    // * `share` - Share of the collected fee credited to the partner (in basis points)
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if the share exceeds 100%
+    pub fn set_partner(e: Env, partner: Address, share: u32) {
+        auth::require_admin(&e);
+        if share > 10_000 {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        storage::set_partner_share(&e, &partner, share);
+    }
+
+    // Remove partner from the registry
+    //
+    // Fees accrued before the removal remain available for claim
+    //
+    // # Arguments
+    //
+    // * `partner` - Partner address
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn remove_partner(e: Env, partner: Address) {
+        auth::require_admin(&e);
+        storage::remove_partner(&e, &partner);
+    }
+
+    // Retrieve partner fees available for claim
+    //
+    // # Arguments
+    //
+    // * `partner` - Partner address
+    //
+    // # Returns
+    //
+    // * Accrued amount of fee tokens
+    pub fn partner_fees(e: Env, partner: Address) -> i128 {
+        storage::get_partner_fees(&e, &partner)
+    }
+
+    // Transfer accrued partner fees to the partner account
+    //
+    // # Arguments
+    //
+    // * `partner` - Partner address
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the partner address
+    //
+    // # Returns
+    //
+    // * Claimed amount of fee tokens
+    pub fn claim_partner_fees(e: Env, partner: Address) -> i128 {
+        partner.require_auth();
+        let amount = storage::get_partner_fees(&e, &partner);
+        if amount > 0 {
+            let fee_token = storage::get_fee_token(&e).unwrap();
+            //release reserved tokens and reset accrued amount
+            release_reserved(&e, &fee_token, amount);
+            storage::set_partner_fees(&e, &partner, 0);
+            token::Client::new(&e, &fee_token).transfer(
+                &e.current_contract_address(),
+                &partner,
+                &amount,
+            );
+        }
+        amount
+    }
+
+    // Set volume-based fee discount tiers schedule
+    //
+    // Trader volume is measured in fee tokens. Swaps where neither side is the fee token count only
+    // if a fee is converted, so fee-exempt and zero-fee swaps of such pairs don't add to the volume
+    //
+    // # Arguments
+    //
+    // * `tiers` - Discount tiers sorted by min cumulative trader volume in ascending order
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if tiers are not sorted or discount exceeds 100%
+    pub fn set_fee_tiers(e: Env, tiers: Vec<FeeTier>) {
+        auth::require_admin(&e);
+        let mut prev: Option<FeeTier> = None;
+        for tier in tiers.iter() {
+            if tier.volume < 0 || tier.discount > 10_000 {
+                panic_with_error!(e, error::BrokerError::InvalidValue);
+            }
+            //each tier should require more volume and provide larger discount than the previous one
+            if let Some(prev) = prev {
+                if tier.volume <= prev.volume || tier.discount < prev.discount {
+                    panic_with_error!(e, error::BrokerError::InvalidValue);
+                }
+            }
+            prev = Some(tier);
+        }
+        storage::set_fee_tiers(&e, &tiers);
+    }
+
+    // Retrieve cumulative trader volume
+    //
+    // # Arguments
+    //
+    // * `trader` - Address of the trader account
+    //
+    // # Returns
+    //
+    // * Total traded volume (in fee tokens), excluding swaps without fee conversion of pairs
+    //   not involving the fee token
+    pub fn trader_volume(e: Env, trader: Address) -> i128 {
+        storage::get_trader_volume(&e, &trader)
+    }
+
+    // Exempt trader from fees or revoke the exemption
+    //
+    // # Arguments
+    //
+    // * `trader` - Address of the trader account
+    // * `exempt` - Whether the trader should be exempt from fees
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn set_trader_exempt(e: Env, trader: Address, exempt: bool) {
+        auth::require_admin(&e);
+        storage::set_trader_exempt(&e, &trader, exempt);
+    }
+
+    // Exempt selling/buying tokens pair from fees or revoke the exemption
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `buying` - Buying token address
+    // * `exempt` - Whether the pair should be exempt from fees
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn set_pair_exempt(e: Env, selling: Address, buying: Address, exempt: bool) {
+        auth::require_admin(&e);
+        storage::set_pair_exempt(&e, &selling, &buying, exempt);
+    }
+
+    // Set router service public key used to verify signed quotes
+    //
+    // # Arguments
+    //
+    // * `key` - Ed25519 public key, or None to remove the signer
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn set_quote_signer(e: Env, key: Option<BytesN<32>>) {
+        auth::require_admin(&e);
+        storage::set_quote_signer(&e, &key);
+    }
+
+    // Require routes and fees to be signed by the router service
+    //
+    // # Arguments
+    //
+    // * `required` - Whether unsigned swaps and orders with trader-supplied fees should be rejected
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn require_signed_quotes(e: Env, required: bool) {
+        auth::require_admin(&e);
+        storage::set_signed_quote_required(&e, required);
+    }
+
+    // Set pools available for on-chain pathfinding
+    //
+    // # Arguments
+    //
+    // * `pools` - Liquidity pools with their tokens, replaces previously registered pools
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if the number of pools exceeds the limit
+    // Panics if a pool has less than two distinct tokens
+    pub fn set_pools(e: Env, pools: Vec<RegisteredPool>) {
+        auth::require_admin(&e);
+        //every registered pool is queried on each pathfinding call
+        if pools.len() > pathfinder::MAX_POOLS {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        for pool in pools.iter() {
+            if pool.tokens.len() < 2 {
+                panic_with_error!(e, error::BrokerError::InvalidValue);
+            }
+            for (index, token) in pool.tokens.iter().enumerate() {
+                if pool.tokens.last_index_of(&token) != Some(index as u32) {
+                    panic_with_error!(e, error::BrokerError::InvalidValue);
+                }
+            }
+        }
+        storage::set_pools(&e, &pools);
+    }
+
+    // Retrieve pools available for on-chain pathfinding
+    //
+    // # Returns
+    //
+    // * Registered liquidity pools
+    pub fn get_pools(e: Env) -> Vec<RegisteredPool> {
+        storage::get_pools(&e)
+    }
+
+    // Set treasury share of the arbitrage profit
+    //
+    // # Arguments
+    //
+    // * `share` - Share of the net profit kept by the contract (in basis points)
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if the share exceeds 100%
+    pub fn set_arbitrage_share(e: Env, share: u32) {
+        auth::require_admin(&e);
+        if share > 10_000 {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        storage::set_arbitrage_share(&e, share);
+    }
+
+    // Set flash swap settings
+    //
+    // # Arguments
+    //
+    // * `config` - Flash swap switch, liquidity cap and flash fee
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if the liquidity cap or the flash fee is zero or exceeds 100%
+    pub fn set_flash_config(e: Env, config: FlashConfig) {
+        auth::require_admin(&e);
+        if config.max_share == 0
+            || config.max_share > 10_000
+            || config.fee == 0
+            || config.fee > 10_000
+        {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        storage::set_flash_config(&e, &config);
+    }
+
+    // Retrieve flash swap settings
+    //
+    // # Returns
+    //
+    // * Flash swap settings, or None if flash swaps were never configured
+    pub fn get_flash_config(e: Env) -> Option<FlashConfig> {
+        storage::get_flash_config(&e)
+    }
+
+    // Perform token swaps following router instructions
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `routes` - Chained swap routes
+    // * `trader` - Address of the trader account
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), ignored if per-protocol fees enabled
+    // * `fpath` - Fee conversion path
+    // * `partner` - Optional address of the integrator sharing the fee
+    // * `hook` - Optional hook contract receiving bought tokens on behalf of the trader
+    // * `recipients` - Optional recipients sharing the bought amount, the trader receives it if empty
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized,
+    // Panics if the caller doesn't match the trader address
+    // Panics if the trader does not have enough balance to perform the swap
+    // Panics if the swap is unfeasible
+    // Panics if the partner is not registered
+    // Panics if the hook contract call fails
+    // Panics if recipient shares don't add up to 100% or recipients are combined with the hook
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        e: Env,
+        selling: Address,
+        routes: Vec<Route>,
+        trader: Address,
+        vfee: u32,
+        ffee: u32,
+        fpath: Vec<PathStep>,
+        partner: Option<Address>,
+        hook: Option<SwapHook>,
+        recipients: Vec<PayoutShare>,
+    ) -> Vec<i128> {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        if !recipients.is_empty() && (hook.is_some() || !are_payout_shares_valid(&recipients)) {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        let request = SwapRequest {
+            selling,
+            routes,
+            trader,
+            vfee,
+            ffee,
+            fpath,
+            partner,
+        };
+        let outcome = execute_swap(&e, &request, Funding::Transfer, None, false, None);
+        match hook {
+            //transfer bought tokens to the hook contract and notify it
+            Some(hook) => hook::pay_out_to_hook(&e, &outcome, &request.trader, &hook),
+            None if recipients.is_empty() => pay_out(&e, &outcome, &request.trader),
+            None => pay_out_shares(&e, &outcome, &recipients),
+        }
+        outcome.to_vec(&e)
+    }
+
+    // Sell one token into several buying tokens at once
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `outputs` - Swap routes grouped by buying token
+    // * `trader` - Address of the trader account
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), replaced by per-protocol fees if enabled
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if no outputs provided or several outputs have the same buying token
+    // Panics if the min amount of any output is not reached
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee for each output
+    pub fn swap_multi_output(
+        e: Env,
+        selling: Address,
+        outputs: Vec<SwapOutput>,
+        trader: Address,
+        vfee: u32,
+        ffee: u32,
+        partner: Option<Address>,
+    ) -> Vec<Vec<i128>> {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        if outputs.is_empty() {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        let mut buying_tokens: Vec<Address> = Vec::new(&e);
+        let mut results = Vec::new(&e);
+        //every output is swapped and verified separately
+        for output in outputs.iter() {
+            let request = SwapRequest {
+                selling: selling.clone(),
+                routes: output.routes,
+                trader: trader.clone(),
+                vfee,
+                ffee,
+                fpath: output.fpath,
+                partner: partner.clone(),
+            };
+            let outcome = execute_swap(&e, &request, Funding::Transfer, None, false, None);
+            if buying_tokens.contains(&outcome.buying) {
+                panic_with_error!(e, error::BrokerError::InvalidValue);
+            }
+            if outcome.bought < output.min {
+                panic_with_error!(e, error::BrokerError::Unfeasible);
+            }
+            pay_out(&e, &outcome, &trader);
+            buying_tokens.push_back(outcome.buying.clone());
+            results.push_back(outcome.to_vec(&e));
+        }
+        results
+    }
+
+    // Sell several tokens into one buying token at once
+    //
+    // # Arguments
+    //
+    // * `inputs` - Swap routes grouped by selling token
+    // * `trader` - Address of the trader account
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), replaced by per-protocol fees if enabled
+    // * `fpath` - Fee conversion path
+    // * `min` - Min total amount of buying tokens to receive after fees
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if no inputs provided or several inputs have the same selling token
+    // Panics if routes of all inputs don't end with the same buying token
+    // Panics if the total min amount is not reached
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee for each input
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_multi_input(
+        e: Env,
+        inputs: Vec<SwapInput>,
+        trader: Address,
+        vfee: u32,
+        ffee: u32,
+        fpath: Vec<PathStep>,
+        min: i128,
+        partner: Option<Address>,
+    ) -> Vec<Vec<i128>> {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        let Some(first) = inputs.first() else {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        };
+        let buying = get_buying_asset(&e, &first.routes);
+        let mut selling_tokens: Vec<Address> = Vec::new(&e);
+        let mut total_bought = 0i128;
+        let mut results = Vec::new(&e);
+        //every input is swapped separately, so each one is verified to be spent exactly
+        for input in inputs.iter() {
+            if input.selling == buying || selling_tokens.contains(&input.selling) {
+                panic_with_error!(e, error::BrokerError::InvalidValue);
+            }
+            if get_buying_asset(&e, &input.routes) != buying {
+                panic_with_error!(e, error::BrokerError::Unfeasible);
+            }
+            let request = SwapRequest {
+                selling: input.selling.clone(),
+                routes: input.routes,
+                trader: trader.clone(),
+                vfee,
+                ffee,
+                fpath: fpath.clone(),
+                partner: partner.clone(),
+            };
+            let outcome = execute_swap(&e, &request, Funding::Transfer, None, false, None);
+            total_bought = total_bought.checked_add(outcome.bought).unwrap();
+            selling_tokens.push_back(input.selling);
+            results.push_back(outcome.to_vec(&e));
+        }
+        if total_bought < min {
+            panic_with_error!(e, error::BrokerError::Unfeasible);
+        }
+        //pay out bought tokens at once
+        if total_bought > 0 {
+            token::Client::new(&e, &buying).transfer(
+                &e.current_contract_address(),
+                &trader,
+                &total_bought,
+            );
+        }
+        results
+    }
+
+    // Rebalance portfolio selling several tokens and buying several other tokens atomically
+    //
+    // # Arguments
+    //
+    // * `inputs` - Swap routes grouped by selling token, each route may end with any target token
+    // * `targets` - Buying tokens with min amounts and fee conversion paths
+    // * `trader` - Address of the trader account
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), replaced by per-protocol fees if enabled
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if inputs or targets are empty, duplicated, or overlap
+    // Panics if any route ends with a token missing from targets
+    // Panics if the min amount of any target is not reached
+    // Panics if net contract balance changes don't match charged fees
+    //
+    // # Returns
+    //
+    // * Net trader balance change for every input (negative) and target (positive) token
+    pub fn rebalance(
+        e: Env,
+        inputs: Vec<SwapInput>,
+        targets: Vec<RebalanceTarget>,
+        trader: Address,
+        vfee: u32,
+        ffee: u32,
+        partner: Option<Address>,
+    ) -> Map<Address, i128> {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        rebalance::rebalance(&e, inputs, targets, &trader, vfee, ffee, partner)
+    }
+
+    // Perform independent token swaps for multiple traders in a single invocation
+    //
+    // # Arguments
+    //
+    // * `requests` - Swap requests, each authorized by its trader
+    //
+    // # Panics
+    //
+    // Panics if the batch is empty
+    // Panics if any trader doesn't authorize the corresponding request
+    // Panics if any of the swaps fails (the whole batch is reverted)
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee for each request
+    pub fn swap_batch(e: Env, requests: Vec<SwapRequest>) -> Vec<Vec<i128>> {
+        if requests.is_empty() {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        let mut results = Vec::new(&e);
+        for request in requests.iter() {
+            //each trader authorizes only its own request
+            request
+                .trader
+                .require_auth_for_args((request.clone(),).into_val(&e));
+            //requests are settled one by one, so balance checks never overlap
+            results.push_back(swap_and_pay_out(&e, request, Funding::Transfer));
+        }
+        results
+    }
+
+    // Perform token swaps skipping routes which failed on the first step and refunding their input to the trader
+    //
+    // A path yielding less than the route min amount counts as failed: routes falling short on a single-step
+    // path are skipped as well, while falling short on the last step of a longer path reverts the swap
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `routes` - Chained swap routes
+    // * `trader` - Address of the trader account
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), replaced by per-protocol fees if enabled
+    // * `fpath` - Fee conversion path
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if all routes failed
+    // Panics if a route failed after its first step, as intermediate tokens can't be refunded
+    // Panics if the min amount scaled to the executed portion is not reached
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee, and indexes of failed routes
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_best_effort(
+        e: Env,
+        selling: Address,
+        routes: Vec<Route>,
+        trader: Address,
+        vfee: u32,
+        ffee: u32,
+        fpath: Vec<PathStep>,
+        partner: Option<Address>,
+    ) -> (Vec<i128>, Vec<u32>) {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        let request = SwapRequest {
+            selling,
+            routes,
+            trader,
+            vfee,
+            ffee,
+            fpath,
+            partner,
+        };
+        //selling tokens of skipped routes are refunded to the trader
+        let outcome = execute_swap(&e, &request, Funding::Transfer, None, true, None);
+        pay_out(&e, &outcome, &request.trader);
+        (outcome.to_vec(&e), outcome.failed)
+    }
+
+    // Perform token swaps re-balancing selling amounts across parallel routes based on current pool reserves
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `routes` - Chained swap routes, amounts of routes going through distinct constant product pools may be re-balanced
+    // * `trader` - Address of the trader account
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), replaced by per-protocol fees if enabled
+    // * `fpath` - Fee conversion path
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if the swap is unfeasible
+    // Panics if the partner is not registered
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_optimized(
+        e: Env,
+        selling: Address,
+        routes: Vec<Route>,
+        trader: Address,
+        vfee: u32,
+        ffee: u32,
+        fpath: Vec<PathStep>,
+        partner: Option<Address>,
+    ) -> Vec<i128> {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        let request = SwapRequest {
+            selling,
+            routes: optimizer::optimize_routes(&e, &routes),
+            trader,
+            vfee,
+            ffee,
+            fpath,
+            partner,
+        };
+        swap_and_pay_out(&e, request, Funding::Transfer)
+    }
+
+    // Perform token swap through the best path found over registered pools
+    //
+    // Paths are ranked by bought amounts estimated from the current pool reserves, so only constant
+    // product pools are considered and other registered pools are ignored. Unlike routed swaps,
+    // no variable fee is charged and the fixed fee is 0 unless replaced by per-protocol fees.
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `buying` - Buying token address
+    // * `amount` - Selling amount
+    // * `min` - Min buying amount
+    // * `trader` - Address of the trader account
+    // * `hops` - Max number of path steps
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if the amount or the max number of hops is invalid
+    // Panics if there is no path between tokens or the swap is unfeasible
+    // Panics if the partner is not registered
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_auto(
+        e: Env,
+        selling: Address,
+        buying: Address,
+        amount: i128,
+        min: i128,
+        trader: Address,
+        hops: u32,
+        partner: Option<Address>,
+    ) -> Vec<i128> {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        if amount <= 0 || min < 0 || hops == 0 || hops > pathfinder::MAX_HOPS || selling == buying {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        let (path, estimated) = pathfinder::find_best_path(&e, &selling, &buying, amount, hops)
+            .unwrap_or_else(|| panic_with_error!(e, error::BrokerError::Unfeasible));
+        //charged fee is a small fraction of the bought amount, so the fee path is ranked by the marginal price
+        let fee_token = storage::get_fee_token(&e).unwrap();
+        let fpath = if fee_token == buying {
+            Vec::new(&e)
+        } else {
+            pathfinder::find_best_path(
+                &e,
+                &buying,
+                &fee_token,
+                (estimated / 100).max(1),
+                pathfinder::MAX_HOPS,
+            )
+            .map(|(fpath, _)| fpath)
+            .unwrap_or_else(|| Vec::new(&e))
+        };
+        let request = SwapRequest {
+            selling,
+            routes: Vec::from_array(
+                &e,
+                [Route {
+                    path,
+                    amount,
+                    min,
+                    estimated,
+                    alternatives: Vec::new(&e),
+                    graph: Vec::new(&e),
+                }],
+            ),
+            trader,
+            vfee: 0,
+            ffee: 0,
+            fpath,
+            partner,
+        };
+        swap_and_pay_out(&e, request, Funding::Transfer)
+    }
+
+    // Execute cyclic routes selling and buying the same token and share the net profit with the treasury
+    //
+    // # Arguments
+    //
+    // * `token` - Token sold and bought by every route
+    // * `routes` - Cyclic swap routes
+    // * `caller` - Address of the account providing the selling amount and receiving the profit
+    // * `min_profit` - Min net profit required for the execution
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the caller address
+    // Panics if routes don't end with the selling token
+    // Panics if the net profit is less than the min profit
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts, caller profit, and treasury profit
+    pub fn arbitrage(
+        e: Env,
+        token: Address,
+        routes: Vec<Route>,
+        caller: Address,
+        min_profit: i128,
+    ) -> Vec<i128> {
+        //require authentication
+        caller.require_auth();
+        //bump only if TTL < 5 days
+        storage::bump_instance(&e, 5);
+        arbitrage::arbitrage(&e, &token, &routes, &caller, min_profit)
+    }
+
+    // Perform token swaps funded by the contract balance, send bought tokens to the receiver contract,
+    // and require the receiver to deliver the selling amount in the callback
+    //
+    // Flash swaps are disabled until the admin enables them with set_flash_config. The selling amount
+    // can't exceed the configured share of the unreserved contract balance, and the receiver should
+    // deliver the sold amount plus the flash fee, which stays on the contract balance.
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `routes` - Chained swap routes
+    // * `initiator` - Address of the flash swap initiator
+    // * `receiver` - Address of the receiver contract implementing the flash swap callback
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), replaced by per-protocol fees if enabled
+    // * `fpath` - Fee conversion path
+    // * `partner` - Optional address of the integrator sharing the fee
+    // * `data` - Arbitrary payload passed to the receiver callback
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the initiator address
+    // Panics if flash swaps are disabled
+    // Panics if the selling amount exceeds the liquidity cap
+    // Panics if the swap is unfeasible
+    // Panics if the receiver didn't deliver the selling amount and the flash fee
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee
+    #[allow(clippy::too_many_arguments)]
+    pub fn flash_swap(
+        e: Env,
+        selling: Address,
+        routes: Vec<Route>,
+        initiator: Address,
+        receiver: Address,
+        vfee: u32,
+        ffee: u32,
+        fpath: Vec<PathStep>,
+        partner: Option<Address>,
+        data: Bytes,
+    ) -> Vec<i128> {
+        //require authentication
+        initiator.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        let request = SwapRequest {
+            selling,
+            routes,
+            trader: initiator,
+            vfee,
+            ffee,
+            fpath,
+            partner,
+        };
+        flash::flash_swap(&e, &request, &receiver, &data)
+    }
+
+    // Swap payer tokens and pay exactly the requested amount to the merchant, refunding the surplus to the payer
+    //
+    // Fees are charged only from the paid amount, the refunded surplus is not charged.
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `routes` - Chained swap routes buying at least the payment amount
+    // * `payer` - Address of the payer account
+    // * `merchant` - Address of the payment recipient
+    // * `amount` - Exact amount of buying tokens to pay
+    // * `invoice` - Invoice reference included in the payment event
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from the paid amount (in ‰), replaced by per-protocol fees if enabled
+    // * `fpath` - Fee conversion path
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the payer address
+    // Panics if the amount is not positive
+    // Panics if the swap is unfeasible or bought amount is less than the payment amount
+    // Panics if the partner is not registered
+    //
+    // # Returns
+    //
+    // * A vector containing sold amount, paid amount, charged fee, and refunded surplus
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_and_pay(
+        e: Env,
+        selling: Address,
+        routes: Vec<Route>,
+        payer: Address,
+        merchant: Address,
+        amount: i128,
+        invoice: Bytes,
+        vfee: u32,
+        ffee: u32,
+        fpath: Vec<PathStep>,
+        partner: Option<Address>,
+    ) -> Vec<i128> {
+        //require authentication
+        payer.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        let request = SwapRequest {
+            selling,
+            routes,
+            trader: payer,
+            vfee,
+            ffee,
+            fpath,
+            partner,
+        };
+        payment::swap_and_pay(&e, &request, &merchant, amount, &invoice)
+    }
+
+    // Perform token swaps pulling selling tokens from the pre-approved trader allowance
+    //
+    // # Arguments
+    //
+    // * `selling` - Selling token address
+    // * `routes` - Chained swap routes
+    // * `trader` - Address of the trader account
+    // * `vfee` - Variable fee charged from actual savings (in ‰)
+    // * `ffee` - Fixed fee charged from total swap amount (in ‰), replaced by per-protocol fees if enabled
+    // * `fpath` - Fee conversion path
+    // * `partner` - Optional address of the integrator sharing the fee
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized,
+    // Panics if the caller doesn't match the trader address
+    // Panics if the trader allowance or balance is not enough to perform the swap
+    // Panics if the swap is unfeasible
+    // Panics if the partner is not registered
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_from(
+        e: Env,
+        selling: Address,
+        routes: Vec<Route>,
+        trader: Address,
+        vfee: u32,
+        ffee: u32,
+        fpath: Vec<PathStep>,
+        partner: Option<Address>,
+    ) -> Vec<i128> {
+        //require authentication
+        trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        swap_and_pay_out(
+            &e,
+            SwapRequest {
+                selling,
+                routes,
+                trader,
+                vfee,
+                ffee,
+                fpath,
+                partner,
+            },
+            Funding::Allowance,
+        )
+    }
+
+    // Perform token swaps following router instructions signed by the router service
+    //
+    // # Arguments
+    //
+    // * `request` - Swap request
+    // * `nonce` - Unique quote nonce
+    // * `expiration` - Ledger sequence after which the quote can't be executed
+    // * `signature` - Ed25519 signature of the quote payload hash
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized,
+    // Panics if the caller doesn't match the trader address
+    // Panics if the quote signer is not set or the signature is invalid
+    // Panics if the quote has expired or the nonce has been already used
+    // Panics if the trader does not have enough balance to perform the swap
+    // Panics if the swap is unfeasible
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts and charged fee
+    pub fn swap_signed(
+        e: Env,
+        request: SwapRequest,
+        nonce: u64,
+        expiration: u32,
+        signature: BytesN<64>,
+    ) -> Vec<i128> {
+        //require authentication
+        request.trader.require_auth();
+        //verify router service signature
+        auth::require_signed_quote(&e, &request, nonce, expiration, &signature);
+        swap_and_pay_out(&e, request, Funding::Transfer)
+    }
+
+    // Perform token swaps authorized by the trader and submitted by a relayer
+    //
+    // # Arguments
+    //
+    // * `request` - Swap request
+    // * `relayer_fee` - Relayer reimbursement charged from the bought amount
+    // * `relayer` - Address of the relayer account
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized,
+    // Panics if the trader didn't authorize the request and relayer fee
+    // Panics if the caller doesn't match the relayer address
+    // Panics if the relayer fee token is neither buying nor fee token
+    // Panics if the trader does not have enough balance to perform the swap
+    // Panics if the swap is unfeasible
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts, charged fee, and relayer reimbursement
+    pub fn swap_relayed(
+        e: Env,
+        request: SwapRequest,
+        relayer_fee: RelayerFee,
+        relayer: Address,
+    ) -> Vec<i128> {
+        //trader authorizes the request and reimbursement regardless of the relayer
+        request
+            .trader
+            .require_auth_for_args((request.clone(), relayer_fee.clone()).into_val(&e));
+        relayer.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        let outcome = execute_swap(
+            &e,
+            &request,
+            Funding::Transfer,
+            Some((relayer, relayer_fee)),
+            false,
+            None,
+        );
+        pay_out(&e, &outcome, &request.trader);
+        let mut result = outcome.to_vec(&e);
+        result.push_back(outcome.relayer_fee);
+        result
+    }
+
+    // Set keeper reward for limit orders execution
+    //
+    // # Arguments
+    //
+    // * `reward` - Share of the bought amount paid to the keeper (in basis points)
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if the reward exceeds 100%
+    pub fn set_keeper_reward(e: Env, reward: u32) {
+        auth::require_admin(&e);
+        if reward > 10_000 {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        storage::set_keeper_reward(&e, reward);
+    }
+
+    // Place limit order escrowing selling tokens on the contract balance
+    //
+    // # Arguments
+    //
+    // * `order` - Limit order
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if the admin requires signed quotes (order fees are set by the trader)
+    // Panics if order parameters are invalid or the order has expired
+    // Panics if the trader does not have enough balance to escrow
+    //
+    // # Returns
+    //
+    // * Order id
+    pub fn place_order(e: Env, order: LimitOrder) -> u64 {
+        order.trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        storage::bump_instance(&e, 5);
+        orders::place_order(&e, &order)
+    }
+
+    // Cancel limit order and refund escrowed tokens
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    //
+    // # Panics
+    //
+    // Panics if the order does not exist
+    // Panics if the caller doesn't match the order trader address
+    pub fn cancel_order(e: Env, order_id: u64) {
+        let order = orders::load_order(&e, order_id);
+        order.trader.require_auth();
+        orders::cancel_order(&e, order_id, &order);
+    }
+
+    // Execute limit order following keeper-supplied routes
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    // * `routes` - Chained swap routes selling the escrowed amount
+    // * `fpath` - Fee conversion path
+    // * `keeper` - Address of the keeper account receiving the reward
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the keeper address
+    // Panics if the admin requires signed quotes (order fees are set by the trader)
+    // Panics if the order does not exist or has expired
+    // Panics if routes don't match the order
+    // Panics if the limit price or min amount is not reached
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts, charged fee, and keeper reward
+    pub fn execute_order(
+        e: Env,
+        order_id: u64,
+        routes: Vec<Route>,
+        fpath: Vec<PathStep>,
+        keeper: Address,
+    ) -> Vec<i128> {
+        keeper.require_auth();
+        //orders placed before signed quotes were required can't bypass the policy
+        auth::require_unsigned_quotes_allowed(&e);
+        orders::execute_order(&e, order_id, routes, fpath, &keeper)
+    }
+
+    // Retrieve limit order
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    //
+    // # Panics
+    //
+    // Panics if the order does not exist
+    pub fn get_order(e: Env, order_id: u64) -> LimitOrder {
+        orders::load_order(&e, order_id)
+    }
+
+    // Set SEP-40 price oracle used to verify order triggers and swap execution prices
+    //
+    // # Arguments
+    //
+    // * `oracle` - Oracle contract address, or None to remove the oracle
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn set_oracle(e: Env, oracle: Option<Address>) {
+        auth::require_admin(&e);
+        storage::set_oracle(&e, &oracle);
+    }
+
+    // Map the token to the SEP-40 oracle asset identifier used for price checks
+    //
+    // Unmapped tokens are looked up as Stellar assets, mapped tokens should always have an oracle price
+    //
+    // # Arguments
+    //
+    // * `token` - Token address
+    // * `asset` - Oracle asset identifier, or None to remove the mapping
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    pub fn set_oracle_asset(e: Env, token: Address, asset: Option<Asset>) {
+        auth::require_admin(&e);
+        storage::set_oracle_asset(&e, &token, &asset);
+    }
+
+    // Set max deviation of swap execution price from the oracle price
+    //
+    // # Arguments
+    //
+    // * `tolerance` - Max unfavorable price deviation (in basis points), or 0 to disable the check
+    //
+    // # Panics
+    //
+    // Panics if the contract is not initialized
+    // Panics if the caller is not the admin
+    // Panics if the tolerance exceeds 100%
+    pub fn set_price_tolerance(e: Env, tolerance: u32) {
+        auth::require_admin(&e);
+        if tolerance > 10_000 {
+            panic_with_error!(e, error::BrokerError::InvalidValue);
+        }
+        storage::set_price_tolerance(&e, tolerance);
+    }
+
+    // Place stop-loss/take-profit order escrowing selling tokens on the contract balance
+    //
+    // # Arguments
+    //
+    // * `order` - Trigger order
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the trader address
+    // Panics if the admin requires signed quotes (order fees are set by the trader)
+    // Panics if order parameters are invalid or the order has expired
+    // Panics if the trader does not have enough balance to escrow
+    //
+    // # Returns
+    //
+    // * Order id
+    pub fn place_trigger_order(e: Env, order: TriggerOrder) -> u64 {
+        order.trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        storage::bump_instance(&e, 5);
+        orders::place_trigger_order(&e, &order)
+    }
+
+    // Cancel trigger order and withdraw escrowed tokens
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    //
+    // # Panics
+    //
+    // Panics if the order does not exist
+    // Panics if the caller doesn't match the order trader address
+    pub fn cancel_trigger_order(e: Env, order_id: u64) {
+        let order = orders::load_trigger_order(&e, order_id);
+        order.trader.require_auth();
+        orders::cancel_trigger_order(&e, order_id, &order);
+    }
+
+    // Execute trigger order following keeper-supplied routes once the oracle price crosses the trigger
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    // * `routes` - Chained swap routes selling the escrowed amount
+    // * `fpath` - Fee conversion path
+    // * `keeper` - Address of the keeper account receiving the reward
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the keeper address
+    // Panics if the admin requires signed quotes (order fees are set by the trader)
+    // Panics if the order does not exist or has expired
+    // Panics if the oracle price is unavailable or hasn't crossed the trigger price
+    // Panics if routes don't match the order
+    // Panics if the order min amount is not reached
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts, charged fee, and keeper reward
+    pub fn execute_trigger_order(
+        e: Env,
+        order_id: u64,
+        routes: Vec<Route>,
+        fpath: Vec<PathStep>,
+        keeper: Address,
+    ) -> Vec<i128> {
+        keeper.require_auth();
+        //orders placed before signed quotes were required can't bypass the policy
+        auth::require_unsigned_quotes_allowed(&e);
+        orders::execute_trigger_order(&e, order_id, routes, fpath, &keeper)
+    }
+
+    // Retrieve trigger order
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
     //
     // # Panics
     //
-    // Panics if the contract is not initialized
-    // Panics if the caller is not the admin
-    pub fn update_contract(e: Env, wasm_hash: BytesN<32>) {
-        auth::require_admin(&e);
-        e.deployer().update_current_contract_wasm(wasm_hash)
+    // Panics if the order does not exist
+    pub fn get_trigger_order(e: Env, order_id: u64) -> TriggerOrder {
+        orders::load_trigger_order(&e, order_id)
     }
 
-    // Perform token swaps following router instructions
+    // Place recurring (DCA) order escrowing the total selling amount on the contract balance
     //
     // # Arguments
     //
-    // * `selling` - Selling token address
-    // * `routes` - Chained swap routes
-    // * `trader` - Address of the trader account
-    // * `vfee` - Variable fee charged from actual savings (in ‰)
-    // * `ffee` - Fixed fee charged from total swap amount (in ‰)
-    // * `fpath` - Fee conversion path
+    // * `order` - Recurring order
     //
     // # Panics
     //
-    // Panics if the contract is not initialized,
     // Panics if the caller doesn't match the trader address
-    // Panics if the trader does not have enough balance to perform the swap
-    // Panics if the swap is unfeasible
+    // Panics if the admin requires signed quotes (order fees are set by the trader)
+    // Panics if order parameters are invalid or the execution period is zero
+    // Panics if the trader does not have enough balance to escrow
     //
     // # Returns
     //
-    // * A vector containing sold/bought amounts and charged fee
-    pub fn swap(
+    // * Order id
+    pub fn place_dca_order(e: Env, order: DcaOrder) -> u64 {
+        order.trader.require_auth();
+        //unsigned quotes may be disabled by the admin
+        auth::require_unsigned_quotes_allowed(&e);
+        storage::bump_instance(&e, 5);
+        orders::place_dca_order(&e, &order)
+    }
+
+    // Remove recurring order and withdraw remaining escrowed tokens
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    //
+    // # Panics
+    //
+    // Panics if the order does not exist
+    // Panics if the caller doesn't match the order trader address
+    //
+    // # Returns
+    //
+    // * Refunded amount of selling tokens
+    pub fn cancel_dca_order(e: Env, order_id: u64) -> i128 {
+        let (order, status) = orders::load_dca_order(&e, order_id);
+        order.trader.require_auth();
+        orders::cancel_dca_order(&e, order_id, &order, &status)
+    }
+
+    // Execute the next recurring order slice following keeper-supplied routes
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    // * `routes` - Chained swap routes selling the slice amount
+    // * `fpath` - Fee conversion path
+    // * `keeper` - Address of the keeper account receiving the reward
+    //
+    // # Panics
+    //
+    // Panics if the caller doesn't match the keeper address
+    // Panics if the admin requires signed quotes (order fees are set by the trader)
+    // Panics if the order does not exist (completed orders are removed after the last slice)
+    // Panics if the execution interval has not elapsed yet
+    // Panics if routes don't match the order
+    // Panics if the min slice price is not reached
+    //
+    // # Returns
+    //
+    // * A vector containing sold/bought amounts, charged fee, and keeper reward
+    pub fn execute_dca_order(
         e: Env,
-        selling: Address,
+        order_id: u64,
         routes: Vec<Route>,
-        trader: Address,
-        vfee: u32,
-        ffee: u32,
         fpath: Vec<PathStep>,
+        keeper: Address,
     ) -> Vec<i128> {
-        //require authentication
-        trader.require_auth();
-        //bump only if TTL < 5 days
-        storage::bump_instance(&e, 5);
+        keeper.require_auth();
+        //orders placed before signed quotes were required can't bypass the policy
+        auth::require_unsigned_quotes_allowed(&e);
+        orders::execute_dca_order(&e, order_id, routes, fpath, &keeper)
+    }
 
+    // Retrieve recurring order
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    //
+    // # Panics
+    //
+    // Panics if the order does not exist or has been completed
+    pub fn get_dca_order(e: Env, order_id: u64) -> DcaOrder {
+        orders::load_dca_order(&e, order_id).0
+    }
+
+    // Retrieve recurring order execution status
+    //
+    // # Arguments
+    //
+    // * `order_id` - Order id
+    //
+    // # Panics
+    //
+    // Panics if the order does not exist or has been completed
+    pub fn get_dca_status(e: Env, order_id: u64) -> DcaStatus {
+        orders::load_dca_order(&e, order_id).1
+    }
+
+    // Withdraw accumulated fees from contract balance
+    //
+    // # Arguments
+    //
+    // * `dest` - Destination account address
+    // * `token` - Token address to withdraw
+    // * `amount` - Amount of tokens to withdraw
+    //
+    // # Panics
+    //
+    // Panics if the caller is not admin
+    // Panics if the amount exceeds unreserved contract balance
+    pub fn withdraw(e: Env, dest: Address, token: Address, amount: i128) {
+        //check admin auth
+        auth::require_admin(&e);
+        //extend TTL if less than 10 days TTL left
+        storage::bump_instance(&e, 10);
         let broker = e.current_contract_address();
-        //estimated bought amount
-        let mut estimated: i128 = 0;
-        //actual bought amount
-        let mut bought: i128 = 0;
+        let token_client = token::Client::new(&e, &token);
+        //reserved tokens (e.g. partner fees) can't be withdrawn
+        let available = token_client
+            .balance(&broker)
+            .checked_sub(storage::get_reserved(&e, &token))
+            .unwrap();
+        if amount > available {
+            panic_with_error!(e, error::BrokerError::ReservedFunds);
+        }
+        //transfer tokens from the contract balance
+        token_client.transfer(&broker, &dest, &amount);
+    }
+}
 
-        //retrieve buying asset, planned amount to sell, and min amount to receive
-        let buying = get_buying_asset(&e, &routes);
-        let fee_token = storage::get_fee_token(&e).unwrap();
-        let (selling_amount, min_buying_amount) = estimate_routes(&routes);
+// Execute swap request on behalf of the trader, leaving bought tokens on the contract balance,
+// fees are charged only from the fee base portion of the bought amount if it is provided
+fn execute_swap(
+    e: &Env,
+    request: &SwapRequest,
+    funding: Funding,
+    relayer: Option<(Address, RelayerFee)>,
+    best_effort: bool,
+    fee_base: Option<i128>,
+) -> SwapOutcome {
+    let SwapRequest {
+        selling,
+        routes,
+        trader,
+        vfee,
+        ffee,
+        fpath,
+        partner,
+    } = request.clone();
+    //bump only if TTL < 5 days
+    storage::bump_instance(e, 5);
 
-        //init token clients for sold/bought tokens
-        let selling_token_client = token::Client::new(&e, &selling);
-        let buying_token_client = token::Client::new(&e, &buying);
-        let fee_token_client = token::Client::new(&e, &fee_token);
-        let fee_balance_before = fee_token_client.balance(&broker);
+    //resolve partner fee share
+    let partner_share = partner.as_ref().map(|partner| {
+        storage::get_partner_share(e, partner)
+            .unwrap_or_else(|| panic_with_error!(e, error::BrokerError::UnknownPartner))
+    });
 
-        //transfer selling asset to contract address to avoid missing trustline errors for the trader
-        selling_token_client.transfer(&trader, &broker, &selling_amount);
+    let broker = e.current_contract_address();
+    //estimated bought amount
+    let mut estimated: i128 = 0;
+    //actual bought amount
+    let mut bought: i128 = 0;
+    //fixed fee based on protocols participating in the swap
+    let mut protocol_fee: i128 = 0;
 
-        //make balances snapshot before swap
-        let selling_balance_before = selling_token_client.balance(&broker);
-        let buying_balance_before = buying_token_client.balance(&broker);
+    //retrieve buying asset, planned amount to sell, and min amount to receive
+    let buying = get_buying_asset(e, &routes);
+    let fee_token = storage::get_fee_token(e).unwrap();
+    let (selling_amount, min_buying_amount) = estimate_routes(&routes);
 
-        //process chained swaps for each route
-        for route in routes.iter() {
-            let swap_result = perform_route_swap(&e, &route, &selling, &broker);
-            //sum actual bought amounts
-            bought = bought.checked_add(swap_result).unwrap();
-            //sum total estimated amounts
-            estimated = estimated.checked_add(route.estimated).unwrap();
-        }
+    //init token clients for sold/bought tokens
+    let selling_token_client = token::Client::new(e, &selling);
+    let buying_token_client = token::Client::new(e, &buying);
+    let fee_token_client = token::Client::new(e, &fee_token);
 
-        if bought < min_buying_amount {
-            panic_with_error!(e, error::BrokerError::Unfeasible);
+    //transfer selling asset to contract address to avoid missing trustline errors for the trader
+    match funding {
+        Funding::Transfer => selling_token_client.transfer(&trader, &broker, &selling_amount),
+        Funding::Allowance => {
+            selling_token_client.transfer_from(&broker, &trader, &broker, &selling_amount)
         }
+        //tokens are already held by the contract
+        Funding::Escrow => {}
+    }
+
+    //make balances snapshot before swap
+    let selling_balance_before = selling_token_client.balance(&broker);
+    let buying_balance_before = buying_token_client.balance(&broker);
+    let fee_balance_before = fee_token_client.balance(&broker);
+    let interim_tokens = get_interim_tokens(
+        e,
+        &routes,
+        &fpath,
+        &Vec::from_array(e, [selling.clone(), buying.clone(), fee_token.clone()]),
+    );
+    let interim_balances_before = get_balances(e, &interim_tokens);
 
-        //calculate trader profit based on estimated
-        let profit = calc_profit(estimated, min_buying_amount, bought);
-
-        let mut selling_balance_after = 0i128;
-        let mut buying_balance_after = 0i128;
-        let mut received_fee = 0i128;
-
-        //charged fee = profit fee + fixed fee
-        let fee = calc_fee(profit, vfee) + calc_fee(bought, ffee);
-        //process fees
-        if fee > 0 {
-            //deduct fee from the execution result
-            bought = bought.checked_sub(fee).unwrap();
-            if fee_token == buying {
-                received_fee = fee;
-                //swap buying asset equals ref fee asset - deduct the fee from the balance variable
-                buying_balance_after = -fee;
-            } else {
-                //convert charged fee to ref fee tokens
-                received_fee = swap_fee(&e, &buying, fee, fpath, &broker);
-                //adjust balance variable in case if selling asset equals ref fee asset
-                if fee_token == selling {
-                    selling_balance_after = -received_fee;
+    //indexes of failed routes skipped in the best effort mode
+    let mut failed: Vec<u32> = Vec::new(e);
+    //actually sold amount
+    let mut sold = selling_amount;
+
+    //process chained swaps for each route
+    for (index, route) in routes.iter().enumerate() {
+        let (swap_result, route_fee) = if best_effort {
+            match try_perform_route_swap(e, &route, &selling, &broker, &trader) {
+                Some(result) => result,
+                None => {
+                    //selling tokens have been refunded, skip the route
+                    failed.push_back(index as u32);
+                    sold = sold.checked_sub(route.amount).unwrap();
+                    continue;
                 }
             }
+        } else {
+            perform_route_swap(e, &route, &selling, &broker)
+        };
+        //sum actual bought amounts
+        bought = bought.checked_add(swap_result).unwrap();
+        //sum fixed fees charged for routing through protocols
+        protocol_fee = protocol_fee.checked_add(route_fee).unwrap();
+        //sum total estimated amounts
+        estimated = estimated.checked_add(route.estimated).unwrap();
+    }
+
+    //scale min amount to the executed portion if some routes failed
+    let min_buying_amount = if sold < selling_amount {
+        if sold == 0 {
+            panic_with_error!(e, error::BrokerError::Unfeasible);
         }
+        min_buying_amount
+            .checked_mul(sold)
+            .unwrap()
+            .checked_div(selling_amount)
+            .unwrap()
+    } else {
+        min_buying_amount
+    };
+    if bought < min_buying_amount {
+        panic_with_error!(e, error::BrokerError::Unfeasible);
+    }
+    //sanity check execution price against the oracle price
+    oracle::verify_execution_price(e, &selling, &buying, sold, bought);
 
-        //verify that exactly selling_amount of tokens were deducted from the balance
-        selling_balance_after = selling_balance_after
-            .checked_add(selling_token_client.balance(&broker))
-            .unwrap();
-        let actual_sold = selling_balance_before.checked_sub(selling_balance_after);
-        if actual_sold.unwrap() != selling_amount {
-            panic_with_error!(e, error::BrokerError::Misconduct);
+    //calculate trader profit based on estimated
+    let profit = calc_profit(estimated, min_buying_amount, bought);
+
+    let mut selling_balance_after = 0i128;
+    let mut buying_balance_after = 0i128;
+    let mut received_fee = 0i128;
+
+    //charged fee = profit fee + fixed fee (exempt traders and pairs are not charged)
+    let mut fee =
+        if storage::is_trader_exempt(e, &trader) || storage::is_pair_exempt(e, &selling, &buying) {
+            0
+        } else if storage::are_protocol_fees_enabled(e) {
+            calc_fee(profit, vfee) + protocol_fee
+        } else {
+            calc_fee(profit, vfee) + calc_fee(bought, ffee)
+        };
+    //scale the fee down to the fee base portion of the bought amount
+    if let Some(base) = fee_base {
+        if base < bought {
+            fee = fee.checked_mul(base).unwrap().checked_div(bought).unwrap();
         }
+    }
+    //apply trader volume discount
+    let volume = storage::get_trader_volume(e, &trader);
+    fee = fee
+        .checked_sub(calc_share(fee, get_fee_discount(e, volume)))
+        .unwrap();
+    //keep gross bought amount for volume tracking
+    let gross_bought = bought;
+    //relayer reimbursement charged from the bought amount
+    let relayer_amount = relayer
+        .as_ref()
+        .map_or(0, |(_, relayer_fee)| relayer_fee.amount);
+    if relayer_amount < 0 {
+        panic_with_error!(e, error::BrokerError::InvalidValue);
+    }
+    //reimbursement in fee tokens is converted together with the charged fee
+    let relayer_conversion = match &relayer {
+        Some((_, relayer_fee)) if relayer_fee.token == buying => 0,
+        Some((_, relayer_fee)) if relayer_fee.token == fee_token => relayer_amount,
+        Some(_) => panic_with_error!(e, error::BrokerError::InvalidValue),
+        None => 0,
+    };
+    //deduct fee and relayer reimbursement from the execution result
+    bought = bought
+        .checked_sub(fee)
+        .unwrap()
+        .checked_sub(relayer_amount)
+        .unwrap();
+    if bought < 0 {
+        panic_with_error!(e, error::BrokerError::Unfeasible);
+    }
+    //amount of buying tokens to convert to ref fee tokens
+    let conversion = fee.checked_add(relayer_conversion).unwrap();
+    let mut relayer_received = 0i128;
+    //process fees
+    if conversion > 0 {
+        if fee_token == buying {
+            received_fee = fee;
+            //swap buying asset equals ref fee asset - deduct the fee from the balance variable
+            buying_balance_after = -fee;
+        } else {
+            //convert charged fee to ref fee tokens
+            let received = swap_fee(e, &buying, conversion, fpath, &broker);
+            //split converted tokens between the broker and relayer
+            relayer_received = received
+                .checked_mul(relayer_conversion)
+                .unwrap()
+                .checked_div(conversion)
+                .unwrap();
+            received_fee = received.checked_sub(relayer_received).unwrap();
+            //adjust balance variable in case if selling asset equals ref fee asset
+            if fee_token == selling {
+                selling_balance_after = -received;
+            }
+        }
+    }
 
-        //verify that received at least min_buying_amount of tokens after the swap
-        buying_balance_after = buying_balance_after
-            .checked_add(buying_token_client.balance(&broker))
+    //verify that exactly selling_amount of tokens were deducted from the balance
+    selling_balance_after = selling_balance_after
+        .checked_add(selling_token_client.balance(&broker))
+        .unwrap();
+    let actual_sold = selling_balance_before.checked_sub(selling_balance_after);
+    if actual_sold.unwrap() != selling_amount {
+        panic_with_error!(e, error::BrokerError::Misconduct);
+    }
+
+    //verify that received at least min_buying_amount of tokens after the swap
+    buying_balance_after = buying_balance_after
+        .checked_add(buying_token_client.balance(&broker))
+        .unwrap();
+    let actual_bought = buying_balance_after
+        .checked_sub(buying_balance_before)
+        .unwrap();
+    if actual_bought < min_buying_amount {
+        panic_with_error!(e, error::BrokerError::Unfeasible);
+    }
+    //verify that reported bought amount has been actually received
+    let relayer_payout = if relayer_conversion > 0 {
+        0
+    } else {
+        relayer_amount
+    };
+    if actual_bought < bought.checked_add(relayer_payout).unwrap() {
+        panic_with_error!(e, error::BrokerError::Misconduct);
+    }
+
+    //verify that fee token balance is correct
+    let mut expected_fee = received_fee.checked_add(relayer_received).unwrap();
+    if expected_fee < 0 {
+        panic_with_error!(e, error::BrokerError::Misconduct);
+    }
+    if fee_token == buying {
+        //bought tokens are still on the contract balance
+        expected_fee = expected_fee
+            .checked_add(bought)
+            .unwrap()
+            .checked_add(relayer_payout)
             .unwrap();
-        let actual_bought = buying_balance_after.checked_sub(buying_balance_before);
-        if actual_bought.unwrap() < min_buying_amount {
-            panic_with_error!(e, error::BrokerError::Unfeasible);
+    }
+    if fee_token == selling {
+        expected_fee = expected_fee.checked_sub(selling_amount).unwrap();
+    }
+    let actual_fee = fee_token_client
+        .balance(&broker)
+        .checked_sub(fee_balance_before)
+        .unwrap();
+    if actual_fee != expected_fee {
+        panic_with_error!(e, error::BrokerError::Misconduct);
+    }
+
+    //verify that intermediate tokens held by the contract were not drained
+    verify_balances(e, &interim_tokens, &interim_balances_before);
+
+    //reimburse the relayer
+    let relayer_paid = if relayer_conversion > 0 {
+        relayer_received
+    } else {
+        relayer_amount
+    };
+    if let Some((relayer, _)) = &relayer {
+        if relayer_conversion > 0 {
+            fee_token_client.transfer(&broker, relayer, &relayer_paid);
+        } else if relayer_paid > 0 {
+            buying_token_client.transfer(&broker, relayer, &relayer_paid);
         }
+    }
 
-        //transfer bought tokens minus fee to the trader account
-        buying_token_client.transfer(&broker, &trader, &bought);
+    //update cumulative trader volume
+    let traded = if fee_token == buying {
+        gross_bought
+    } else if fee_token == selling {
+        sold
+    } else if conversion > 0 {
+        //estimate volume based on the fee conversion rate
+        gross_bought
+            .checked_mul(received_fee.checked_add(relayer_received).unwrap())
+            .unwrap()
+            .checked_div(conversion)
+            .unwrap()
+    } else {
+        //no conversion rate to the fee token, swap doesn't count towards fee tiers
+        0
+    };
+    if traded > 0 {
+        storage::set_trader_volume(e, &trader, volume.checked_add(traded).unwrap());
+    }
 
-        //verify that fee token balance is correct
-        let actual_fee = fee_token_client
-            .balance(&broker)
-            .checked_sub(fee_balance_before)
-            .unwrap();
-        if actual_fee != received_fee || actual_fee < 0 {
-            panic_with_error!(e, error::BrokerError::Misconduct);
+    //credit partner share of the received fee
+    if let (Some(partner), Some(share)) = (partner, partner_share) {
+        let partner_fee = calc_share(received_fee, share);
+        if partner_fee > 0 {
+            reserve(e, &fee_token, partner_fee);
+            let accrued = storage::get_partner_fees(e, &partner);
+            storage::set_partner_fees(e, &partner, accrued.checked_add(partner_fee).unwrap());
         }
+    }
 
-        //return result as array
-        Vec::from_array(&e, [selling_amount, bought, received_fee])
+    SwapOutcome {
+        buying,
+        sold,
+        bought,
+        fee: received_fee,
+        relayer_fee: relayer_paid,
+        failed,
     }
+}
 
-    // Withdraw accumulated fees from contract balance
-    //
-    // # Arguments
-    //
-    // * `dest` - Destination account address
-    // * `token` - Token address to withdraw
-    // * `amount` - Amount of tokens to withdraw
-    //
-    // # Panics
-    //
-    // Panics if the caller is not admin
-    pub fn withdraw(e: Env, dest: Address, token: Address, amount: i128) {
-        //check admin auth
-        auth::require_admin(&e);
-        //extend TTL if less than 10 days TTL left
-        storage::bump_instance(&e, 10);
-        //transfer tokens from the contract balance
-        let token_client = token::Client::new(&e, &token);
-        token_client.transfer(&e.current_contract_address(), &dest, &amount);
+// Execute swap request and transfer bought tokens to the trader
+fn swap_and_pay_out(e: &Env, request: SwapRequest, funding: Funding) -> Vec<i128> {
+    let outcome = execute_swap(e, &request, funding, None, false, None);
+    pay_out(e, &outcome, &request.trader);
+    outcome.to_vec(e)
+}
+
+// Transfer bought tokens minus fee to the recipient account
+fn pay_out(e: &Env, outcome: &SwapOutcome, to: &Address) {
+    if outcome.bought > 0 {
+        token::Client::new(e, &outcome.buying).transfer(
+            &e.current_contract_address(),
+            to,
+            &outcome.bought,
+        );
+    }
+}
+
+// Split bought tokens between recipients, the last recipient receives the rounding remainder
+fn pay_out_shares(e: &Env, outcome: &SwapOutcome, recipients: &Vec<PayoutShare>) {
+    let token_client = token::Client::new(e, &outcome.buying);
+    let broker = e.current_contract_address();
+    let mut remaining = outcome.bought;
+    for (index, payout) in recipients.iter().enumerate() {
+        let amount = if index as u32 == recipients.len() - 1 {
+            remaining
+        } else {
+            calc_share(outcome.bought, payout.share)
+        };
+        remaining = remaining.checked_sub(amount).unwrap();
+        if amount > 0 {
+            token_client.transfer(&broker, &payout.recipient, &amount);
+        }
+    }
+}
+
+// Check that every recipient share is positive and shares add up to 100%
+fn are_payout_shares_valid(recipients: &Vec<PayoutShare>) -> bool {
+    let mut total: u32 = 0;
+    for payout in recipients.iter() {
+        if payout.share == 0 {
+            return false;
+        }
+        total = total.saturating_add(payout.share);
     }
+    total == 10_000
 }
 
-// Execute chained swap based on provided route
-fn perform_route_swap(e: &Env, swap: &Route, selling: &Address, to: &Address) -> i128 {
+// Execute chained swap based on provided route, returns bought amount and protocols fixed fee amount
+//
+// Alternative paths are tried only while nothing has been swapped, so every path except the last one
+// should consist of a single step, a multi-step path falling short after its first step reverts the swap
+fn perform_route_swap(e: &Env, swap: &Route, selling: &Address, to: &Address) -> (i128, i128) {
+    //execute route graph with split and merged amounts
+    if !swap.graph.is_empty() {
+        return graph::perform_graph_swap(e, swap, selling, to);
+    }
+    //try primary and alternative paths in order if the router provided fallbacks
+    if !swap.alternatives.is_empty() {
+        for path in get_route_paths(e, swap).iter() {
+            match try_execute_path(e, &path, swap.amount, swap.min, selling, to) {
+                Ok(amount) => return (amount, calc_fee(amount, get_route_protocol_fee(e, &path))),
+                //nothing has been swapped yet, try the next path
+                Err(0) => continue,
+                //intermediate tokens can't be swapped back to the selling token
+                Err(_) => panic_with_error!(e, error::BrokerError::Unfeasible),
+            }
+        }
+        panic_with_error!(e, error::BrokerError::Unfeasible);
+    }
     //current amount = initial selling amount
     let mut amount = swap.amount;
     //current token = initial selling token address
@@ -228,7 +1924,7 @@ fn perform_route_swap(e: &Env, swap: &Route, selling: &Address, to: &Address) ->
     //iterate and execute swap path steps
     for path_step in swap.path.iter() {
         let buying = path_step.asset.clone();
-        let protocol = path_step.protocol.clone();
+        let protocol = path_step.protocol;
         let swap_info = LPSwap {
             step: path_step,
             in_token,
@@ -236,12 +1932,117 @@ fn perform_route_swap(e: &Env, swap: &Route, selling: &Address, to: &Address) ->
             to: to.clone(),
         };
         //execute the swap, set current amount = swapped amount
-        amount = swap_adapter(&e, protocol, swap_info);
+        amount = swap_adapter(e, protocol, swap_info);
         //current token = bought token address
         in_token = buying;
     }
     //return result amount
-    amount
+    (
+        amount,
+        calc_fee(amount, get_route_protocol_fee(e, &swap.path)),
+    )
+}
+
+// Try to execute chained swap based on provided route, refunds selling tokens and returns None
+// if the route failed before any swap happened
+fn try_perform_route_swap(
+    e: &Env,
+    swap: &Route,
+    selling: &Address,
+    to: &Address,
+    refund_to: &Address,
+) -> Option<(i128, i128)> {
+    //route graph can't be partially reverted, so it either succeeds or fails the whole swap
+    if !swap.graph.is_empty() {
+        return Some(graph::perform_graph_swap(e, swap, selling, to));
+    }
+    //try primary and alternative paths in order
+    for path in get_route_paths(e, swap).iter() {
+        match try_execute_path(e, &path, swap.amount, swap.min, selling, to) {
+            Ok(amount) => {
+                return Some((amount, calc_fee(amount, get_route_protocol_fee(e, &path))))
+            }
+            //nothing has been swapped yet, try the next path
+            Err(0) => continue,
+            //intermediate tokens can't be swapped back to the selling token, revert the whole swap
+            Err(_) => panic_with_error!(e, error::BrokerError::Unfeasible),
+        }
+    }
+    //all paths failed before the first swap, refund selling tokens
+    token::Client::new(e, selling).transfer(to, refund_to, &swap.amount);
+    None
+}
+
+// Retrieve primary and alternative route paths in the execution order
+fn get_route_paths(e: &Env, swap: &Route) -> Vec<Vec<PathStep>> {
+    let mut paths = Vec::from_array(e, [swap.path.clone()]);
+    paths.append(&swap.alternatives);
+    //paths followed by fallbacks should be single-step, otherwise the fallbacks could never run
+    for path in paths.iter().take(swap.alternatives.len() as usize) {
+        if path.len() > 1 {
+            panic_with_error!(e, error::BrokerError::InvalidPath);
+        }
+    }
+    paths
+}
+
+// Try to execute chained swap path, returns bought amount on success, or the index of the failed step
+fn try_execute_path(
+    e: &Env,
+    path: &Vec<PathStep>,
+    amount: i128,
+    min: i128,
+    selling: &Address,
+    to: &Address,
+) -> Result<i128, u32> {
+    //current amount = initial selling amount
+    let mut amount = amount;
+    //current token = initial selling token address
+    let mut in_token = selling.clone();
+    let last_index = path.len().saturating_sub(1);
+    //iterate and execute swap path steps
+    for (index, path_step) in path.iter().enumerate() {
+        let buying = path_step.asset.clone();
+        let protocol = path_step.protocol;
+        //the last step should yield at least the route min amount
+        let step_min = if index as u32 == last_index {
+            min.max(1)
+        } else {
+            1
+        };
+        let swap_info = LPSwap {
+            step: path_step,
+            in_token: in_token.clone(),
+            amount,
+            to: to.clone(),
+        };
+        //failed step is rolled back, so tokens obtained by previous steps are still held
+        match try_swap_adapter(e, protocol, swap_info, step_min) {
+            Some(bought) => amount = bought,
+            None => return Err(index as u32),
+        }
+        //current token = bought token address
+        in_token = buying;
+    }
+    //return result amount
+    Ok(amount)
+}
+
+// Sum fixed fees of all distinct protocols participating in the route (in ‰)
+fn get_route_protocol_fee(e: &Env, path: &Vec<PathStep>) -> u32 {
+    let mut fee: u32 = 0;
+    let mut charged_protocols: u32 = 0;
+    for path_step in path.iter() {
+        //charge protocol fee only once per route
+        let protocol_mask = 1u32 << (path_step.protocol as u32);
+        if charged_protocols & protocol_mask == 0 {
+            charged_protocols |= protocol_mask;
+            fee = fee
+                .checked_add(storage::get_protocol_fee(e, &path_step.protocol))
+                .unwrap();
+        }
+    }
+    fee
 }
 
 // Calculate variable fee based on the difference between actual and estimated swap amounts
@@ -265,6 +2066,40 @@ fn calc_fee(amount: i128, share: u32) -> i128 {
         .unwrap()
 }
 
+// Calculate amount share based on the basis points value
+fn calc_share(amount: i128, bps: u32) -> i128 {
+    amount
+        .checked_mul(bps as i128)
+        .unwrap()
+        .checked_div(10_000) //share specified in basis points
+        .unwrap()
+}
+
+// Find fee discount (in basis points) for the cumulative trader volume
+fn get_fee_discount(e: &Env, volume: i128) -> u32 {
+    let mut discount = 0;
+    //tiers are sorted by volume in ascending order
+    for tier in storage::get_fee_tiers(e).iter() {
+        if volume < tier.volume {
+            break;
+        }
+        discount = tier.discount;
+    }
+    discount
+}
+
+// Reserve tokens on the contract balance
+fn reserve(e: &Env, token: &Address, amount: i128) {
+    let reserved = storage::get_reserved(e, token);
+    storage::set_reserved(e, token, reserved.checked_add(amount).unwrap());
+}
+
+// Release previously reserved tokens
+fn release_reserved(e: &Env, token: &Address, amount: i128) {
+    let reserved = storage::get_reserved(e, token);
+    storage::set_reserved(e, token, reserved.checked_sub(amount).unwrap());
+}
+
 // Convert charged fee to ref fee tokens
 fn swap_fee(e: &Env, selling: &Address, fee: i128, path: Vec<PathStep>, broker: &Address) -> i128 {
     //skip for zero fee
@@ -277,9 +2112,71 @@ fn swap_fee(e: &Env, selling: &Address, fee: i128, path: Vec<PathStep>, broker:
         amount: fee,
         estimated: 1,
         min: 1,
+        alternatives: Vec::new(e),
+        graph: Vec::new(e),
     };
     //convert fee to the ref fee tokens
-    perform_route_swap(&e, &fee_route, &selling, &broker)
+    perform_route_swap(e, &fee_route, selling, broker).0
+}
+
+// Collect intermediate tokens from routes and fee path, skipping excluded tokens
+fn get_interim_tokens(
+    e: &Env,
+    routes: &Vec<Route>,
+    fpath: &Vec<PathStep>,
+    excluded: &Vec<Address>,
+) -> Vec<Address> {
+    let mut tokens: Vec<Address> = Vec::new(e);
+    let mut add_path = |path: Vec<PathStep>| {
+        for step in path.iter() {
+            if !excluded.contains(&step.asset) && !tokens.contains(&step.asset) {
+                tokens.push_back(step.asset);
+            }
+        }
+    };
+    for route in routes.iter() {
+        add_path(route.path);
+        for alternative in route.alternatives.iter() {
+            add_path(alternative);
+        }
+        for graph_step in route.graph.iter() {
+            add_path(Vec::from_array(e, [graph_step.step]));
+        }
+    }
+    add_path(fpath.clone());
+    tokens
+}
+
+// Retrieve contract balances of given tokens
+fn get_balances(e: &Env, tokens: &Vec<Address>) -> Vec<i128> {
+    let broker = e.current_contract_address();
+    let mut balances = Vec::new(e);
+    for token in tokens.iter() {
+        balances.push_back(token::Client::new(e, &token).balance(&broker));
+    }
+    balances
+}
+
+// Verify that contract balances of given tokens didn't decrease
+fn verify_balances(e: &Env, tokens: &Vec<Address>, before: &Vec<i128>) {
+    let after = get_balances(e, tokens);
+    for (balance_after, balance_before) in after.iter().zip(before.iter()) {
+        if balance_after < balance_before {
+            panic_with_error!(e, error::BrokerError::Misconduct);
+        }
+    }
+}
+
+// Verify that contract balances of given tokens changed exactly by expected amounts
+fn verify_balance_deltas(e: &Env, tokens: &Vec<Address>, before: &Vec<i128>, expected: &Vec<i128>) {
+    let after = get_balances(e, tokens);
+    for ((balance_after, balance_before), delta) in
+        after.iter().zip(before.iter()).zip(expected.iter())
+    {
+        if balance_after.checked_sub(balance_before).unwrap() != delta {
+            panic_with_error!(e, error::BrokerError::Misconduct);
+        }
+    }
 }
 
 // Retrieve the target token and the total amounts
@@ -301,13 +2198,11 @@ fn get_buying_asset(e: &Env, routes: &Vec<Route>) -> Address {
     let mut asset: Option<Address> = None;
     //check every route
     for route in routes.iter() {
-        let last_step = route.path.last();
-        if last_step.is_none() {
+        let route_asset = get_route_asset(e, &route);
+        if route_asset.is_none() {
             //zero length path
             panic_with_error!(&e, error::BrokerError::Unfeasible);
         }
-        //retrieve the asset
-        let route_asset = Some(last_step.unwrap().asset.clone());
         //assign the token variable
         if asset.is_none() {
             asset = route_asset;
@@ -315,6 +2210,12 @@ fn get_buying_asset(e: &Env, routes: &Vec<Route>) -> Address {
             //each route should have the same buying token
             panic_with_error!(&e, error::BrokerError::Unfeasible);
         }
+        //alternative paths should end with the same buying token
+        for alternative in route.alternatives.iter() {
+            if alternative.last().map(|step| step.asset) != asset {
+                panic_with_error!(&e, error::BrokerError::Unfeasible);
+            }
+        }
     }
     //at least one is required
     if asset.is_none() {
@@ -322,3 +2223,12 @@ fn get_buying_asset(e: &Env, routes: &Vec<Route>) -> Address {
     }
     asset.unwrap()
 }
+
+// Retrieve buying token address of the route
+fn get_route_asset(e: &Env, route: &Route) -> Option<Address> {
+    if route.graph.is_empty() {
+        route.path.last().map(|step| step.asset)
+    } else {
+        graph::get_graph_buying_asset(e, &route.graph)
+    }
+}
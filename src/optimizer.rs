@@ -0,0 +1,143 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::adapters::adapter::{estimate_amount_out, get_adapter_reserves};
+use crate::types::{poolreserves::PoolReserves, route::Route};
+
+// Max number of split re-balancing iterations
+const MAX_ITERATIONS: u32 = 24;
+
+// Re-distribute selling amounts across parallel routes based on current pool reserves, keeping the total
+//
+// Only linear routes where every step goes through a constant product pool are re-balanced,
+// other routes keep their amounts. Routes are estimated independently, so nothing is re-balanced
+// if eligible routes share a pool. Re-balanced amounts are applied only if their estimate beats
+// the original split.
+pub fn optimize_routes(e: &Env, routes: &Vec<Route>) -> Vec<Route> {
+    //collect reserves of eligible routes
+    let mut indexes: Vec<u32> = Vec::new(e);
+    let mut pools: Vec<Vec<PoolReserves>> = Vec::new(e);
+    let mut amounts: Vec<i128> = Vec::new(e);
+    let mut used_pools: Vec<Address> = Vec::new(e);
+    for (index, route) in routes.iter().enumerate() {
+        if let Some(reserves) = get_route_reserves(e, &route) {
+            //shared pool liquidity would be counted separately for each route
+            for step in route.path.iter() {
+                if used_pools.contains(&step.pool) {
+                    return routes.clone();
+                }
+            }
+            for step in route.path.iter() {
+                used_pools.push_back(step.pool);
+            }
+            indexes.push_back(index as u32);
+            pools.push_back(reserves);
+            amounts.push_back(route.amount);
+        }
+    }
+    //nothing to re-balance
+    if indexes.len() < 2 {
+        return routes.clone();
+    }
+    let mut total: i128 = 0;
+    for amount in amounts.iter() {
+        total = total.checked_add(amount).unwrap();
+    }
+    let original_amounts = amounts.clone();
+    //move a chunk from the route with the lowest marginal loss to the route with the highest marginal gain,
+    //halving the chunk once no move improves the total output
+    let mut chunk = total
+        .checked_div(indexes.len() as i128 * 2)
+        .unwrap_or_default();
+    for _ in 0..MAX_ITERATIONS {
+        if chunk == 0 {
+            break;
+        }
+        let mut receiver: Option<(u32, i128)> = None;
+        for (i, reserves) in pools.iter().enumerate() {
+            let amount = amounts.get(i as u32).unwrap();
+            let gain = estimate_path(&reserves, amount.checked_add(chunk).unwrap())
+                .checked_sub(estimate_path(&reserves, amount))
+                .unwrap();
+            if receiver.is_none_or(|(_, best)| gain > best) {
+                receiver = Some((i as u32, gain));
+            }
+        }
+        let (receiver, gain) = receiver.unwrap();
+        let mut donor: Option<(u32, i128)> = None;
+        for (i, reserves) in pools.iter().enumerate() {
+            let amount = amounts.get(i as u32).unwrap();
+            //every route should keep a positive amount
+            if i as u32 == receiver || amount <= chunk {
+                continue;
+            }
+            let loss = estimate_path(&reserves, amount)
+                .checked_sub(estimate_path(&reserves, amount.checked_sub(chunk).unwrap()))
+                .unwrap();
+            if donor.is_none_or(|(_, best)| loss < best) {
+                donor = Some((i as u32, loss));
+            }
+        }
+        match donor {
+            Some((donor, loss)) if gain > loss => {
+                let amount = amounts.get(donor).unwrap();
+                amounts.set(donor, amount.checked_sub(chunk).unwrap());
+                let amount = amounts.get(receiver).unwrap();
+                amounts.set(receiver, amount.checked_add(chunk).unwrap());
+            }
+            _ => chunk /= 2,
+        }
+    }
+    //keep the original split unless re-balanced amounts yield more
+    if estimate_split(&pools, &amounts) <= estimate_split(&pools, &original_amounts) {
+        return routes.clone();
+    }
+    //apply re-balanced amounts
+    let mut result = routes.clone();
+    for (i, index) in indexes.iter().enumerate() {
+        let route = Route {
+            amount: amounts.get(i as u32).unwrap(),
+            ..routes.get(index).unwrap()
+        };
+        result.set(index, route);
+    }
+    result
+}
+
+// Retrieve reserves of all route pools, returns None if the route can't be estimated
+fn get_route_reserves(e: &Env, route: &Route) -> Option<Vec<PoolReserves>> {
+    if route.amount <= 0
+        || route.path.is_empty()
+        || !route.alternatives.is_empty()
+        || !route.graph.is_empty()
+    {
+        return None;
+    }
+    let mut reserves = Vec::new(e);
+    for step in route.path.iter() {
+        let pool = get_adapter_reserves(e, &step)?;
+        //empty pools can't be estimated
+        if pool.reserve_in <= 0 || pool.reserve_out <= 0 {
+            return None;
+        }
+        reserves.push_back(pool);
+    }
+    Some(reserves)
+}
+
+// Estimate total bought amount of parallel routes
+fn estimate_split(pools: &Vec<Vec<PoolReserves>>, amounts: &Vec<i128>) -> i128 {
+    let mut total: i128 = 0;
+    for (reserves, amount) in pools.iter().zip(amounts.iter()) {
+        total = total.checked_add(estimate_path(&reserves, amount)).unwrap();
+    }
+    total
+}
+
+// Estimate bought amount of the chained swap
+fn estimate_path(reserves: &Vec<PoolReserves>, amount: i128) -> i128 {
+    let mut amount = amount;
+    for pool in reserves.iter() {
+        amount = estimate_amount_out(&pool, amount);
+    }
+    amount
+}
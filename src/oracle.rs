@@ -0,0 +1,126 @@
+use soroban_sdk::{contractclient, panic_with_error, token, Address, Env};
+
+use crate::storage;
+use crate::types::{
+    error::BrokerError,
+    oracle::{Asset, PriceData},
+};
+
+// Max age of the oracle price record (in seconds)
+const MAX_PRICE_AGE: u64 = 900;
+// Cross price scale factor (7 decimals)
+const PRICE_SCALE: i128 = 10_000_000;
+
+#[contractclient(name = "PriceOracleClient")]
+#[allow(dead_code)]
+pub trait PriceOracleTrait {
+    fn decimals() -> u32;
+    fn lastprice(asset: Asset) -> Option<PriceData>;
+}
+
+// Retrieve the price of the asset in the oracle base asset, returns None if the price is missing or outdated
+fn try_get_asset_price(e: &Env, client: &PriceOracleClient, asset: &Asset) -> Option<i128> {
+    let data = client.lastprice(asset)?;
+    if data.price <= 0 || e.ledger().timestamp().saturating_sub(data.timestamp) > MAX_PRICE_AGE {
+        return None;
+    }
+    Some(data.price)
+}
+
+// Retrieve the price of the token in the oracle base asset
+//
+// Tokens mapped to oracle assets should have a valid price, otherwise the token is looked up
+// as a Stellar asset and None is returned if the oracle doesn't quote it
+fn get_token_price(e: &Env, client: &PriceOracleClient, token: &Address) -> Option<i128> {
+    match storage::get_oracle_asset(e, token) {
+        Some(asset) => Some(
+            try_get_asset_price(e, client, &asset)
+                .unwrap_or_else(|| panic_with_error!(e, BrokerError::PriceUnavailable)),
+        ),
+        None => try_get_asset_price(e, client, &Asset::Stellar(token.clone())),
+    }
+}
+
+// Convert the selling amount to buying tokens at oracle prices
+//
+// Oracle prices are quoted per whole token unit, while amounts are expressed in the smallest token units,
+// so the result is adjusted for the difference in token decimals
+fn convert_amount(
+    e: &Env,
+    selling: &Address,
+    buying: &Address,
+    amount: i128,
+    selling_price: i128,
+    buying_price: i128,
+) -> i128 {
+    let selling_decimals = token::Client::new(e, selling).decimals();
+    let buying_decimals = token::Client::new(e, buying).decimals();
+    //oracle decimals cancel out
+    let mut value = amount.checked_mul(selling_price).unwrap();
+    let mut base = buying_price;
+    if buying_decimals > selling_decimals {
+        value = value
+            .checked_mul(10i128.pow(buying_decimals - selling_decimals))
+            .unwrap();
+    } else {
+        base = base
+            .checked_mul(10i128.pow(selling_decimals - buying_decimals))
+            .unwrap();
+    }
+    value.checked_div(base).unwrap()
+}
+
+// Retrieve the oracle price of the selling token in buying tokens, panics if any price is unavailable
+//
+// Tokens not mapped to oracle assets are looked up as Stellar assets.
+// The price uses the same units as the limit order price: smallest buying token units
+// per smallest selling token unit, scaled by 10^7.
+pub fn get_cross_price(e: &Env, selling: &Address, buying: &Address) -> i128 {
+    let oracle = storage::get_oracle(e)
+        .unwrap_or_else(|| panic_with_error!(e, BrokerError::PriceUnavailable));
+    let client = PriceOracleClient::new(e, &oracle);
+    let (Some(selling_price), Some(buying_price)) = (
+        get_token_price(e, &client, selling),
+        get_token_price(e, &client, buying),
+    ) else {
+        panic_with_error!(e, BrokerError::PriceUnavailable);
+    };
+    convert_amount(e, selling, buying, PRICE_SCALE, selling_price, buying_price)
+}
+
+// Verify that the swap execution price (before fees) is not worse than the oracle price beyond the tolerance
+//
+// Tokens resolve to oracle assets the same way as for the cross price. The check is skipped
+// if an unmapped token is not quoted by the oracle.
+pub fn verify_execution_price(
+    e: &Env,
+    selling: &Address,
+    buying: &Address,
+    sold: i128,
+    bought: i128,
+) {
+    let tolerance = storage::get_price_tolerance(e);
+    if tolerance == 0 {
+        return;
+    }
+    let Some(oracle) = storage::get_oracle(e) else {
+        return;
+    };
+    let client = PriceOracleClient::new(e, &oracle);
+    let (Some(selling_price), Some(buying_price)) = (
+        get_token_price(e, &client, selling),
+        get_token_price(e, &client, buying),
+    ) else {
+        return;
+    };
+    let expected = convert_amount(e, selling, buying, sold, selling_price, buying_price);
+    //allow execution price to be worse than the oracle price within the tolerance
+    let min = expected
+        .checked_mul(10_000 - tolerance as i128)
+        .unwrap()
+        .checked_div(10_000)
+        .unwrap();
+    if bought < min {
+        panic_with_error!(e, BrokerError::PriceDeviation);
+    }
+}
@@ -0,0 +1,273 @@
+use soroban_sdk::{panic_with_error, token, Address, Env, Vec};
+
+use crate::types::{
+    dcaorder::{DcaOrder, DcaStatus},
+    error::BrokerError,
+    order::LimitOrder,
+    outcome::Funding,
+    route::Route,
+    step::PathStep,
+    swaprequest::SwapRequest,
+    triggerorder::{TriggerKind, TriggerOrder},
+};
+use crate::{
+    calc_share, estimate_routes, execute_swap, get_buying_asset, oracle, release_reserved, reserve,
+    storage,
+};
+
+// Price scale factor (7 decimals)
+const PRICE_SCALE: i128 = 10_000_000;
+
+// Escrow selling tokens and store the order
+pub fn place_order(e: &Env, order: &LimitOrder) -> u64 {
+    if order.amount <= 0 || order.price < 0 || order.min < 0 || order.selling == order.buying {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    if order.expiration < e.ledger().sequence() {
+        panic_with_error!(e, BrokerError::OrderExpired);
+    }
+    escrow(e, &order.trader, &order.selling, order.amount);
+    //store the order
+    let id = storage::next_order_id(e);
+    storage::set_order(e, id, order);
+    id
+}
+
+// Load order or panic if it doesn't exist
+pub fn load_order(e: &Env, id: u64) -> LimitOrder {
+    storage::get_order(e, id).unwrap_or_else(|| panic_with_error!(e, BrokerError::OrderNotFound))
+}
+
+// Remove the order and refund escrowed tokens
+pub fn cancel_order(e: &Env, id: u64, order: &LimitOrder) {
+    storage::remove_order(e, id);
+    refund(e, &order.trader, &order.selling, order.amount);
+}
+
+// Execute the order using routes supplied by the keeper
+pub fn execute_order(
+    e: &Env,
+    id: u64,
+    routes: Vec<Route>,
+    fpath: Vec<PathStep>,
+    keeper: &Address,
+) -> Vec<i128> {
+    let order = load_order(e, id);
+    if order.expiration < e.ledger().sequence() {
+        panic_with_error!(e, BrokerError::OrderExpired);
+    }
+    //routes should sell exactly the escrowed amount for the order buying token
+    verify_routes(e, &routes, order.amount, &order.buying);
+    //release escrow and execute the swap
+    storage::remove_order(e, id);
+    release_reserved(e, &order.selling, order.amount);
+    let request = SwapRequest {
+        selling: order.selling.clone(),
+        routes,
+        trader: order.trader.clone(),
+        vfee: order.vfee,
+        ffee: order.ffee,
+        fpath,
+        partner: None,
+    };
+    settle(e, &request, order.price, order.min, keeper)
+}
+
+// Escrow selling tokens and store the recurring order
+pub fn place_dca_order(e: &Env, order: &DcaOrder) -> u64 {
+    //zero period would allow executing all slices in the same ledger
+    if order.amount <= 0
+        || order.slice <= 0
+        || order.slice > order.amount
+        || order.period == 0
+        || order.price < 0
+        || order.selling == order.buying
+    {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    escrow(e, &order.trader, &order.selling, order.amount);
+    //store the order, first slice can be executed immediately
+    let id = storage::next_order_id(e);
+    let status = DcaStatus {
+        remaining: order.amount,
+        bought: 0,
+        executions: 0,
+        next: e.ledger().sequence(),
+    };
+    storage::set_dca_order(e, id, order, &status);
+    id
+}
+
+// Load recurring order or panic if it doesn't exist
+pub fn load_dca_order(e: &Env, id: u64) -> (DcaOrder, DcaStatus) {
+    let order = storage::get_dca_order(e, id);
+    let status = storage::get_dca_status(e, id);
+    match (order, status) {
+        (Some(order), Some(status)) => (order, status),
+        _ => panic_with_error!(e, BrokerError::OrderNotFound),
+    }
+}
+
+// Remove the recurring order and refund remaining escrowed tokens
+pub fn cancel_dca_order(e: &Env, id: u64, order: &DcaOrder, status: &DcaStatus) -> i128 {
+    let remaining = status.remaining;
+    storage::remove_dca_order(e, id);
+    if remaining > 0 {
+        refund(e, &order.trader, &order.selling, remaining);
+    }
+    remaining
+}
+
+// Execute the next recurring order slice using routes supplied by the keeper
+pub fn execute_dca_order(
+    e: &Env,
+    id: u64,
+    routes: Vec<Route>,
+    fpath: Vec<PathStep>,
+    keeper: &Address,
+) -> Vec<i128> {
+    let (order, mut status) = load_dca_order(e, id);
+    if e.ledger().sequence() < status.next {
+        panic_with_error!(e, BrokerError::OrderNotReady);
+    }
+    //routes should sell exactly one slice (or the remaining amount) for the order buying token
+    let slice = order.slice.min(status.remaining);
+    verify_routes(e, &routes, slice, &order.buying);
+    //update order status and release the slice from escrow
+    status.remaining = status.remaining.checked_sub(slice).unwrap();
+    status.executions += 1;
+    status.next = e.ledger().sequence().checked_add(order.period).unwrap();
+    release_reserved(e, &order.selling, slice);
+    let request = SwapRequest {
+        selling: order.selling.clone(),
+        routes,
+        trader: order.trader.clone(),
+        vfee: order.vfee,
+        ffee: order.ffee,
+        fpath,
+        partner: None,
+    };
+    let result = settle(e, &request, order.price, 0, keeper);
+    status.bought = status.bought.checked_add(result.get(1).unwrap()).unwrap();
+    //completed order is removed like a cancelled one
+    if status.remaining == 0 {
+        storage::remove_dca_order(e, id);
+    } else {
+        storage::set_dca_order(e, id, &order, &status);
+    }
+    result
+}
+
+// Escrow selling tokens and store the trigger order
+pub fn place_trigger_order(e: &Env, order: &TriggerOrder) -> u64 {
+    if order.amount <= 0 || order.trigger <= 0 || order.min < 0 || order.selling == order.buying {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    if order.expiration < e.ledger().sequence() {
+        panic_with_error!(e, BrokerError::OrderExpired);
+    }
+    escrow(e, &order.trader, &order.selling, order.amount);
+    //store the order
+    let id = storage::next_order_id(e);
+    storage::set_trigger_order(e, id, order);
+    id
+}
+
+// Load trigger order or panic if it doesn't exist
+pub fn load_trigger_order(e: &Env, id: u64) -> TriggerOrder {
+    storage::get_trigger_order(e, id)
+        .unwrap_or_else(|| panic_with_error!(e, BrokerError::OrderNotFound))
+}
+
+// Remove the trigger order and refund escrowed tokens
+pub fn cancel_trigger_order(e: &Env, id: u64, order: &TriggerOrder) {
+    storage::remove_trigger_order(e, id);
+    refund(e, &order.trader, &order.selling, order.amount);
+}
+
+// Execute the trigger order once the oracle price crosses the trigger price
+pub fn execute_trigger_order(
+    e: &Env,
+    id: u64,
+    routes: Vec<Route>,
+    fpath: Vec<PathStep>,
+    keeper: &Address,
+) -> Vec<i128> {
+    let order = load_trigger_order(e, id);
+    if order.expiration < e.ledger().sequence() {
+        panic_with_error!(e, BrokerError::OrderExpired);
+    }
+    //check trigger condition against the oracle price
+    let price = oracle::get_cross_price(e, &order.selling, &order.buying);
+    let triggered = match order.kind {
+        TriggerKind::StopLoss => price <= order.trigger,
+        TriggerKind::TakeProfit => price >= order.trigger,
+    };
+    if !triggered {
+        panic_with_error!(e, BrokerError::OrderNotReady);
+    }
+    //routes should sell exactly the escrowed amount for the order buying token
+    verify_routes(e, &routes, order.amount, &order.buying);
+    //release escrow and execute the swap
+    storage::remove_trigger_order(e, id);
+    release_reserved(e, &order.selling, order.amount);
+    let request = SwapRequest {
+        selling: order.selling.clone(),
+        routes,
+        trader: order.trader.clone(),
+        vfee: order.vfee,
+        ffee: order.ffee,
+        fpath,
+        partner: None,
+    };
+    settle(e, &request, 0, order.min, keeper)
+}
+
+// Transfer selling tokens from the trader and keep them in escrow
+fn escrow(e: &Env, trader: &Address, token: &Address, amount: i128) {
+    token::Client::new(e, token).transfer(trader, &e.current_contract_address(), &amount);
+    reserve(e, token, amount);
+}
+
+// Release escrowed tokens back to the trader
+fn refund(e: &Env, trader: &Address, token: &Address, amount: i128) {
+    release_reserved(e, token, amount);
+    token::Client::new(e, token).transfer(&e.current_contract_address(), trader, &amount);
+}
+
+// Verify that routes sell given amount for the buying token
+fn verify_routes(e: &Env, routes: &Vec<Route>, amount: i128, buying: &Address) {
+    let (selling_amount, _) = estimate_routes(routes);
+    if selling_amount != amount {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    if &get_buying_asset(e, routes) != buying {
+        panic_with_error!(e, BrokerError::Unfeasible);
+    }
+}
+
+// Swap escrowed tokens, verify the price, and pay out the trader and the keeper
+fn settle(e: &Env, request: &SwapRequest, price: i128, min: i128, keeper: &Address) -> Vec<i128> {
+    let outcome = execute_swap(e, request, Funding::Escrow, None, false, None);
+    //keeper reward is charged from the bought amount
+    let reward = calc_share(outcome.bought, storage::get_keeper_reward(e));
+    let received = outcome.bought.checked_sub(reward).unwrap();
+    //verify limit price and min amount
+    let min_by_price = outcome
+        .sold
+        .checked_mul(price)
+        .unwrap()
+        .checked_div(PRICE_SCALE)
+        .unwrap();
+    if received < min || received < min_by_price {
+        panic_with_error!(e, BrokerError::Unfeasible);
+    }
+    //pay out bought tokens and keeper reward
+    let buying_token_client = token::Client::new(e, &outcome.buying);
+    let broker = e.current_contract_address();
+    buying_token_client.transfer(&broker, &request.trader, &received);
+    if reward > 0 {
+        buying_token_client.transfer(&broker, keeper, &reward);
+    }
+    Vec::from_array(e, [outcome.sold, received, outcome.fee, reward])
+}
@@ -0,0 +1,126 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::adapters::adapter::{estimate_amount_out, get_adapter_reserves};
+use crate::storage;
+use crate::types::{poolreserves::PoolReserves, step::PathStep};
+
+// Max number of hops in paths found on-chain
+pub const MAX_HOPS: u32 = 3;
+// Max number of pools registered for on-chain pathfinding
+pub const MAX_POOLS: u32 = 20;
+
+// Find the path over registered pools yielding the max bought amount, returns the path and its estimate
+//
+// Only constant product pools can be quoted, other registered pools are ignored.
+// Bought amounts are estimated from the current pool reserves, the actual swap result may differ.
+pub fn find_best_path(
+    e: &Env,
+    selling: &Address,
+    buying: &Address,
+    amount: i128,
+    hops: u32,
+) -> Option<(Vec<PathStep>, i128)> {
+    let edges = get_edges(e);
+    let mut best: Option<(Vec<PathStep>, i128)> = None;
+    let mut path: Vec<PathStep> = Vec::new(e);
+    let mut visited = Vec::from_array(e, [selling.clone()]);
+    search(
+        &edges,
+        selling,
+        buying,
+        amount,
+        hops,
+        &mut path,
+        &mut visited,
+        &mut best,
+    );
+    best
+}
+
+// Walk all paths up to the given depth and keep the best one
+#[allow(clippy::too_many_arguments)]
+fn search(
+    edges: &Vec<(Address, PathStep, PoolReserves)>,
+    token: &Address,
+    buying: &Address,
+    amount: i128,
+    hops: u32,
+    path: &mut Vec<PathStep>,
+    visited: &mut Vec<Address>,
+    best: &mut Option<(Vec<PathStep>, i128)>,
+) {
+    for (in_token, step, reserves) in edges.iter() {
+        //skip other tokens and loops
+        if &in_token != token || visited.contains(&step.asset) {
+            continue;
+        }
+        let bought = estimate_amount_out(&reserves, amount);
+        if bought <= 0 {
+            continue;
+        }
+        path.push_back(step.clone());
+        if &step.asset == buying {
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_amount)| bought > *best_amount)
+            {
+                *best = Some((path.clone(), bought));
+            }
+        } else if hops > 1 {
+            visited.push_back(step.asset.clone());
+            search(
+                edges,
+                &step.asset,
+                buying,
+                bought,
+                hops - 1,
+                path,
+                visited,
+                best,
+            );
+            visited.pop_back();
+        }
+        path.pop_back();
+    }
+}
+
+// Build swap directions of registered pools with current reserves
+fn get_edges(e: &Env) -> Vec<(Address, PathStep, PoolReserves)> {
+    let mut edges = Vec::new(e);
+    for pool in storage::get_pools(e).iter() {
+        for si in 0..pool.tokens.len() {
+            for bi in (si + 1)..pool.tokens.len() {
+                let selling = pool.tokens.get(si).unwrap();
+                let buying = pool.tokens.get(bi).unwrap();
+                let step = PathStep {
+                    protocol: pool.protocol,
+                    asset: buying.clone(),
+                    pool: pool.pool.clone(),
+                    si,
+                    bi,
+                };
+                //reserves are retrieved once per pair, reversed for the opposite direction
+                let reserves = match get_adapter_reserves(e, &step) {
+                    Some(reserves) if reserves.reserve_in > 0 && reserves.reserve_out > 0 => {
+                        reserves
+                    }
+                    _ => continue,
+                };
+                let reverse_step = PathStep {
+                    asset: selling.clone(),
+                    si: bi,
+                    bi: si,
+                    ..step.clone()
+                };
+                let reverse_reserves = PoolReserves {
+                    reserve_in: reserves.reserve_out,
+                    reserve_out: reserves.reserve_in,
+                    ..reserves.clone()
+                };
+                edges.push_back((selling, step, reserves));
+                edges.push_back((buying, reverse_step, reverse_reserves));
+            }
+        }
+    }
+    edges
+}
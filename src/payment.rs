@@ -0,0 +1,38 @@
+use soroban_sdk::{panic_with_error, symbol_short, token, Address, Bytes, Env, Vec};
+
+use crate::execute_swap;
+use crate::types::{error::BrokerError, outcome::Funding, swaprequest::SwapRequest};
+
+// Swap payer tokens, pay exactly the requested amount to the merchant, and refund the surplus to the payer
+pub fn swap_and_pay(
+    e: &Env,
+    request: &SwapRequest,
+    merchant: &Address,
+    amount: i128,
+    invoice: &Bytes,
+) -> Vec<i128> {
+    if amount <= 0 {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    let outcome = execute_swap(e, request, Funding::Transfer, None, false, Some(amount));
+    //routes should overshoot the payment amount
+    let surplus = outcome.bought.checked_sub(amount).unwrap();
+    if surplus < 0 {
+        panic_with_error!(e, BrokerError::Unfeasible);
+    }
+    let broker = e.current_contract_address();
+    let buying_token_client = token::Client::new(e, &outcome.buying);
+    buying_token_client.transfer(&broker, merchant, &amount);
+    if surplus > 0 {
+        buying_token_client.transfer(&broker, &request.trader, &surplus);
+    }
+    e.events().publish(
+        (
+            symbol_short!("payment"),
+            merchant.clone(),
+            request.trader.clone(),
+        ),
+        (outcome.buying.clone(), amount, invoice.clone()),
+    );
+    Vec::from_array(e, [outcome.sold, amount, outcome.fee, surplus])
+}
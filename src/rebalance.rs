@@ -0,0 +1,110 @@
+use soroban_sdk::{panic_with_error, token, Address, Env, Map, Vec};
+
+use crate::types::{
+    error::BrokerError, outcome::Funding, rebalancetarget::RebalanceTarget, route::Route,
+    swapinput::SwapInput, swaprequest::SwapRequest,
+};
+use crate::{
+    estimate_routes, execute_swap, get_balances, get_interim_tokens, get_route_asset, storage,
+    verify_balance_deltas, verify_balances,
+};
+
+// Sell input tokens and buy target tokens atomically, returns net trader balance change per token
+pub fn rebalance(
+    e: &Env,
+    inputs: Vec<SwapInput>,
+    targets: Vec<RebalanceTarget>,
+    trader: &Address,
+    vfee: u32,
+    ffee: u32,
+    partner: Option<Address>,
+) -> Map<Address, i128> {
+    if inputs.is_empty() || targets.is_empty() {
+        panic_with_error!(e, BrokerError::InvalidValue);
+    }
+    //collect all distinct tokens participating in the rebalance
+    let fee_token = storage::get_fee_token(e).unwrap();
+    let mut tokens: Vec<Address> = Vec::new(e);
+    let mut all_routes: Vec<Route> = Vec::new(e);
+    for input in inputs.iter() {
+        if tokens.contains(&input.selling) {
+            panic_with_error!(e, BrokerError::InvalidValue);
+        }
+        tokens.push_back(input.selling);
+        all_routes.append(&input.routes);
+    }
+    let mut fpaths = Vec::new(e);
+    for target in targets.iter() {
+        if tokens.contains(&target.buying) {
+            panic_with_error!(e, BrokerError::InvalidValue);
+        }
+        tokens.push_back(target.buying);
+        fpaths.append(&target.fpath);
+    }
+    if !tokens.contains(&fee_token) {
+        tokens.push_back(fee_token.clone());
+    }
+    //make balances snapshot before any swap
+    let balances_before = get_balances(e, &tokens);
+    let interim_tokens = get_interim_tokens(e, &all_routes, &fpaths, &tokens);
+    let interim_balances_before = get_balances(e, &interim_tokens);
+
+    let mut received: Map<Address, i128> = Map::new(e);
+    let mut result: Map<Address, i128> = Map::new(e);
+    let mut total_fee = 0i128;
+    for input in inputs.iter() {
+        let mut sold = 0i128;
+        for target in targets.iter() {
+            //routes of the input ending with the target token
+            let mut routes: Vec<Route> = Vec::new(e);
+            for route in input.routes.iter() {
+                if get_route_asset(e, &route) == Some(target.buying.clone()) {
+                    routes.push_back(route);
+                }
+            }
+            if routes.is_empty() {
+                continue;
+            }
+            let request = SwapRequest {
+                selling: input.selling.clone(),
+                routes,
+                trader: trader.clone(),
+                vfee,
+                ffee,
+                fpath: target.fpath.clone(),
+                partner: partner.clone(),
+            };
+            let outcome = execute_swap(e, &request, Funding::Transfer, None, false, None);
+            sold = sold.checked_add(outcome.sold).unwrap();
+            total_fee = total_fee.checked_add(outcome.fee).unwrap();
+            let bought = received.get(target.buying.clone()).unwrap_or_default();
+            received.set(target.buying, bought.checked_add(outcome.bought).unwrap());
+        }
+        //every route should end with one of the targets
+        let (declared, _) = estimate_routes(&input.routes);
+        if sold != declared {
+            panic_with_error!(e, BrokerError::Unfeasible);
+        }
+        result.set(input.selling, sold.checked_neg().unwrap());
+    }
+    //verify min amounts and pay out bought tokens
+    let broker = e.current_contract_address();
+    for target in targets.iter() {
+        let bought = received.get(target.buying.clone()).unwrap_or_default();
+        if bought < target.min {
+            panic_with_error!(e, BrokerError::Unfeasible);
+        }
+        if bought > 0 {
+            token::Client::new(e, &target.buying).transfer(&broker, trader, &bought);
+        }
+        result.set(target.buying, bought);
+    }
+    //only charged fees can remain on the contract balance
+    let mut expected = Vec::new(e);
+    for token in tokens.iter() {
+        expected.push_back(if token == fee_token { total_fee } else { 0 });
+    }
+    verify_balance_deltas(e, &tokens, &balances_before, &expected);
+    verify_balances(e, &interim_tokens, &interim_balances_before);
+    result
+}
@@ -1,15 +1,66 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
 
 use crate::types;
+use crate::types::dcaorder::{DcaOrder, DcaStatus};
+use crate::types::feetier::FeeTier;
+use crate::types::flashconfig::FlashConfig;
+use crate::types::oracle::Asset;
+use crate::types::order::LimitOrder;
 use crate::types::protocol::Protocol;
+use crate::types::registeredpool::RegisteredPool;
+use crate::types::triggerorder::TriggerOrder;
 
 use types::error::BrokerError;
 
 const ADMIN_KEY: &str = "admin"; //admin key
 const FEE_TOKEN_KEY: &str = "ft"; //fee token key
+const FEE_TIERS_KEY: &str = "tiers"; //fee discount tiers key
+const PROTOCOL_FEES_KEY: &str = "pfees"; //per-protocol fees switch key
+const QUOTE_SIGNER_KEY: &str = "qsigner"; //quote signer public key
+const SIGNED_ONLY_KEY: &str = "qsigned"; //signed quotes requirement key
+const ORDER_ID_KEY: &str = "oid"; //last order id key
+const KEEPER_REWARD_KEY: &str = "kreward"; //keeper reward key
+const ORACLE_KEY: &str = "oracle"; //price oracle address key
+const PRICE_TOLERANCE_KEY: &str = "ptol"; //oracle price deviation tolerance key
+const ARBITRAGE_SHARE_KEY: &str = "arbshare"; //treasury share of arbitrage profit key
+const FLASH_CONFIG_KEY: &str = "flash"; //flash swap settings key
 
 const LPH: u32 = 720; //estimated ledgers per hour
 
+// Keys for per-address contract data
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    // Registered partner fee share (in basis points)
+    Partner(Address),
+    // Partner fees accrued for claim
+    PartnerFees(Address),
+    // Token amount reserved on the contract balance
+    Reserved(Address),
+    // Cumulative trader volume (in fee tokens)
+    Volume(Address),
+    // Trader exempt from fees
+    ExemptTrader(Address),
+    // Selling/buying tokens pair exempt from fees
+    ExemptPair(Address, Address),
+    // Fixed fee charged for routing through the protocol (in ‰)
+    ProtocolFee(Protocol),
+    // Used signed quote nonce
+    Nonce(Address, u64),
+    // Limit order
+    Order(u64),
+    // Recurring order
+    Dca(u64),
+    // Recurring order execution status
+    DcaStatus(u64),
+    // Stop-loss/take-profit order
+    Trigger(u64),
+    // Oracle asset identifier of the token
+    OracleAsset(Address),
+    // Pools available for on-chain pathfinding
+    Pools,
+}
+
 // Initialize contract settings
 pub fn init_settings(e: &Env, admin: &Address, fee_token: &Address) {
     let storage = e.storage().instance();
@@ -41,9 +92,396 @@ pub fn is_protocol_enabled(e: &Env, protocol: &Protocol) -> bool {
     e.storage().instance().get(protocol).unwrap_or_default()
 }
 
+// Register partner with the given fee share
+pub fn set_partner_share(e: &Env, partner: &Address, share: u32) {
+    let key = DataKey::Partner(partner.clone());
+    e.storage().persistent().set(&key, &share);
+    bump_persistent(e, &key);
+}
+
+// Remove partner from the registry
+pub fn remove_partner(e: &Env, partner: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Partner(partner.clone()));
+}
+
+// Retrieve partner fee share
+pub fn get_partner_share(e: &Env, partner: &Address) -> Option<u32> {
+    let key = DataKey::Partner(partner.clone());
+    let share = e.storage().persistent().get(&key);
+    if share.is_some() {
+        bump_persistent(e, &key);
+    }
+    share
+}
+
+// Retrieve partner fees available for claim
+pub fn get_partner_fees(e: &Env, partner: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::PartnerFees(partner.clone()))
+        .unwrap_or_default()
+}
+
+// Update partner fees available for claim
+pub fn set_partner_fees(e: &Env, partner: &Address, amount: i128) {
+    let key = DataKey::PartnerFees(partner.clone());
+    if amount == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &amount);
+    bump_persistent(e, &key);
+}
+
+// Retrieve token amount reserved on the contract balance
+pub fn get_reserved(e: &Env, token: &Address) -> i128 {
+    e.storage()
+        .instance()
+        .get(&DataKey::Reserved(token.clone()))
+        .unwrap_or_default()
+}
+
+// Update token amount reserved on the contract balance
+pub fn set_reserved(e: &Env, token: &Address, amount: i128) {
+    e.storage()
+        .instance()
+        .set(&DataKey::Reserved(token.clone()), &amount);
+}
+
+// Set fixed fee for routing through the protocol
+pub fn set_protocol_fee(e: &Env, protocol: &Protocol, fee: u32) {
+    e.storage()
+        .instance()
+        .set(&DataKey::ProtocolFee(*protocol), &fee);
+}
+
+// Retrieve fixed fee for routing through the protocol
+pub fn get_protocol_fee(e: &Env, protocol: &Protocol) -> u32 {
+    e.storage()
+        .instance()
+        .get(&DataKey::ProtocolFee(*protocol))
+        .unwrap_or_default()
+}
+
+// Enable/disable per-protocol fixed fees
+pub fn set_protocol_fees_enabled(e: &Env, enabled: bool) {
+    e.storage().instance().set(&PROTOCOL_FEES_KEY, &enabled);
+}
+
+// Check whether per-protocol fixed fees replace the caller-supplied fixed fee
+pub fn are_protocol_fees_enabled(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&PROTOCOL_FEES_KEY)
+        .unwrap_or_default()
+}
+
+// Set or remove router service quote signer public key
+pub fn set_quote_signer(e: &Env, key: &Option<BytesN<32>>) {
+    match key {
+        Some(key) => e.storage().instance().set(&QUOTE_SIGNER_KEY, key),
+        None => e.storage().instance().remove(&QUOTE_SIGNER_KEY),
+    }
+}
+
+// Retrieve router service quote signer public key
+pub fn get_quote_signer(e: &Env) -> Option<BytesN<32>> {
+    e.storage().instance().get(&QUOTE_SIGNER_KEY)
+}
+
+// Set whether only signed quotes can be executed
+pub fn set_signed_quote_required(e: &Env, required: bool) {
+    e.storage().instance().set(&SIGNED_ONLY_KEY, &required);
+}
+
+// Check whether only signed quotes can be executed
+pub fn is_signed_quote_required(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&SIGNED_ONLY_KEY)
+        .unwrap_or_default()
+}
+
+// Mark quote nonce as used until the quote expiration
+pub fn use_nonce(e: &Env, trader: &Address, nonce: u64, expiration: u32) {
+    let key = DataKey::Nonce(trader.clone(), nonce);
+    let storage = e.storage().temporary();
+    if storage.has(&key) {
+        e.panic_with_error(BrokerError::NonceUsed);
+    }
+    storage.set(&key, &true);
+    let ttl = expiration - e.ledger().sequence();
+    storage.extend_ttl(&key, ttl, ttl);
+}
+
+// Generate new order id
+pub fn next_order_id(e: &Env) -> u64 {
+    let id = e
+        .storage()
+        .instance()
+        .get::<_, u64>(&ORDER_ID_KEY)
+        .unwrap_or_default()
+        + 1;
+    e.storage().instance().set(&ORDER_ID_KEY, &id);
+    id
+}
+
+// Store limit order until its expiration
+pub fn set_order(e: &Env, id: u64, order: &LimitOrder) {
+    let key = DataKey::Order(id);
+    e.storage().persistent().set(&key, order);
+    bump_until(e, &key, order.expiration);
+}
+
+// Retrieve limit order
+pub fn get_order(e: &Env, id: u64) -> Option<LimitOrder> {
+    e.storage().persistent().get(&DataKey::Order(id))
+}
+
+// Remove limit order
+pub fn remove_order(e: &Env, id: u64) {
+    e.storage().persistent().remove(&DataKey::Order(id));
+}
+
+// Store recurring order and its status until the next execution
+pub fn set_dca_order(e: &Env, id: u64, order: &DcaOrder, status: &DcaStatus) {
+    let order_key = DataKey::Dca(id);
+    let status_key = DataKey::DcaStatus(id);
+    e.storage().persistent().set(&order_key, order);
+    e.storage().persistent().set(&status_key, status);
+    bump_until(e, &order_key, status.next);
+    bump_until(e, &status_key, status.next);
+}
+
+// Retrieve recurring order
+pub fn get_dca_order(e: &Env, id: u64) -> Option<DcaOrder> {
+    e.storage().persistent().get(&DataKey::Dca(id))
+}
+
+// Retrieve recurring order status
+pub fn get_dca_status(e: &Env, id: u64) -> Option<DcaStatus> {
+    e.storage().persistent().get(&DataKey::DcaStatus(id))
+}
+
+// Remove recurring order and its status
+pub fn remove_dca_order(e: &Env, id: u64) {
+    e.storage().persistent().remove(&DataKey::Dca(id));
+    e.storage().persistent().remove(&DataKey::DcaStatus(id));
+}
+
+// Store trigger order until its expiration
+pub fn set_trigger_order(e: &Env, id: u64, order: &TriggerOrder) {
+    let key = DataKey::Trigger(id);
+    e.storage().persistent().set(&key, order);
+    bump_until(e, &key, order.expiration);
+}
+
+// Retrieve trigger order
+pub fn get_trigger_order(e: &Env, id: u64) -> Option<TriggerOrder> {
+    e.storage().persistent().get(&DataKey::Trigger(id))
+}
+
+// Remove trigger order
+pub fn remove_trigger_order(e: &Env, id: u64) {
+    e.storage().persistent().remove(&DataKey::Trigger(id));
+}
+
+// Set SEP-40 price oracle address
+pub fn set_oracle(e: &Env, oracle: &Option<Address>) {
+    match oracle {
+        Some(oracle) => e.storage().instance().set(&ORACLE_KEY, oracle),
+        None => e.storage().instance().remove(&ORACLE_KEY),
+    }
+}
+
+// Retrieve SEP-40 price oracle address
+pub fn get_oracle(e: &Env) -> Option<Address> {
+    e.storage().instance().get(&ORACLE_KEY)
+}
+
+// Map the token to the oracle asset identifier or remove the mapping
+pub fn set_oracle_asset(e: &Env, token: &Address, asset: &Option<Asset>) {
+    let key = DataKey::OracleAsset(token.clone());
+    match asset {
+        Some(asset) => {
+            e.storage().persistent().set(&key, asset);
+            bump_persistent(e, &key);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+// Retrieve the oracle asset identifier of the token
+pub fn get_oracle_asset(e: &Env, token: &Address) -> Option<Asset> {
+    let key = DataKey::OracleAsset(token.clone());
+    let asset = e.storage().persistent().get(&key);
+    if asset.is_some() {
+        bump_persistent(e, &key);
+    }
+    asset
+}
+
+// Set max execution price deviation from the oracle price
+pub fn set_price_tolerance(e: &Env, tolerance: u32) {
+    e.storage().instance().set(&PRICE_TOLERANCE_KEY, &tolerance);
+}
+
+// Retrieve max execution price deviation from the oracle price
+pub fn get_price_tolerance(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&PRICE_TOLERANCE_KEY)
+        .unwrap_or_default()
+}
+
+// Set pools available for on-chain pathfinding
+pub fn set_pools(e: &Env, pools: &Vec<RegisteredPool>) {
+    let key = DataKey::Pools;
+    e.storage().persistent().set(&key, pools);
+    bump_persistent(e, &key);
+}
+
+// Retrieve pools available for on-chain pathfinding
+pub fn get_pools(e: &Env) -> Vec<RegisteredPool> {
+    let key = DataKey::Pools;
+    match e.storage().persistent().get(&key) {
+        Some(pools) => {
+            bump_persistent(e, &key);
+            pools
+        }
+        None => Vec::new(e),
+    }
+}
+
+// Set keeper reward for order execution
+pub fn set_keeper_reward(e: &Env, reward: u32) {
+    e.storage().instance().set(&KEEPER_REWARD_KEY, &reward);
+}
+
+// Retrieve keeper reward for order execution
+pub fn get_keeper_reward(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&KEEPER_REWARD_KEY)
+        .unwrap_or_default()
+}
+
+// Set treasury share of arbitrage profit
+pub fn set_arbitrage_share(e: &Env, share: u32) {
+    e.storage().instance().set(&ARBITRAGE_SHARE_KEY, &share);
+}
+
+// Retrieve treasury share of arbitrage profit
+pub fn get_arbitrage_share(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&ARBITRAGE_SHARE_KEY)
+        .unwrap_or_default()
+}
+
+// Set flash swap settings
+pub fn set_flash_config(e: &Env, config: &FlashConfig) {
+    e.storage().instance().set(&FLASH_CONFIG_KEY, config);
+}
+
+// Retrieve flash swap settings
+pub fn get_flash_config(e: &Env) -> Option<FlashConfig> {
+    e.storage().instance().get(&FLASH_CONFIG_KEY)
+}
+
+// Update fee discount tiers schedule
+pub fn set_fee_tiers(e: &Env, tiers: &Vec<FeeTier>) {
+    e.storage().instance().set(&FEE_TIERS_KEY, tiers);
+}
+
+// Retrieve fee discount tiers schedule
+pub fn get_fee_tiers(e: &Env) -> Vec<FeeTier> {
+    e.storage()
+        .instance()
+        .get(&FEE_TIERS_KEY)
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+// Retrieve cumulative trader volume
+pub fn get_trader_volume(e: &Env, trader: &Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::Volume(trader.clone()))
+        .unwrap_or_default()
+}
+
+// Update cumulative trader volume
+pub fn set_trader_volume(e: &Env, trader: &Address, volume: i128) {
+    let key = DataKey::Volume(trader.clone());
+    e.storage().persistent().set(&key, &volume);
+    bump_persistent(e, &key);
+}
+
+// Exempt trader from fees or revoke the exemption
+pub fn set_trader_exempt(e: &Env, trader: &Address, exempt: bool) {
+    set_flag(e, &DataKey::ExemptTrader(trader.clone()), exempt);
+}
+
+// Check whether the trader is exempt from fees
+pub fn is_trader_exempt(e: &Env, trader: &Address) -> bool {
+    get_flag(e, &DataKey::ExemptTrader(trader.clone()))
+}
+
+// Exempt selling/buying tokens pair from fees or revoke the exemption
+pub fn set_pair_exempt(e: &Env, selling: &Address, buying: &Address, exempt: bool) {
+    set_flag(
+        e,
+        &DataKey::ExemptPair(selling.clone(), buying.clone()),
+        exempt,
+    );
+}
+
+// Check whether the selling/buying tokens pair is exempt from fees
+pub fn is_pair_exempt(e: &Env, selling: &Address, buying: &Address) -> bool {
+    get_flag(e, &DataKey::ExemptPair(selling.clone(), buying.clone()))
+}
+
 // Extend TTL for 30 days if less than X days TTL left
 pub fn bump_instance(e: &Env, days_left: u32) {
     let min = LPH * 24 * days_left;
     let extend = LPH * 24 * 30;
     e.storage().instance().extend_ttl(min, extend);
 }
+
+// Store persistent flag (only enabled flags are kept in storage)
+fn set_flag(e: &Env, key: &DataKey, enabled: bool) {
+    if enabled {
+        e.storage().persistent().set(key, &true);
+        bump_persistent(e, key);
+    } else {
+        e.storage().persistent().remove(key);
+    }
+}
+
+// Check whether the persistent flag is enabled
+fn get_flag(e: &Env, key: &DataKey) -> bool {
+    let enabled = e.storage().persistent().has(key);
+    if enabled {
+        bump_persistent(e, key);
+    }
+    enabled
+}
+
+// Extend persistent entry TTL to outlive the given ledger (at least 30 days)
+fn bump_until(e: &Env, key: &DataKey, ledger: u32) {
+    let mut extend = LPH * 24 * 30;
+    let required = ledger.saturating_sub(e.ledger().sequence()) + 1;
+    if required > extend {
+        extend = required.min(e.storage().max_ttl());
+    }
+    e.storage().persistent().extend_ttl(key, extend, extend);
+}
+
+// Extend persistent entry TTL for 30 days if less than 10 days TTL left
+fn bump_persistent(e: &Env, key: &DataKey) {
+    let min = LPH * 24 * 10;
+    let extend = LPH * 24 * 30;
+    e.storage().persistent().extend_ttl(key, min, extend);
+}
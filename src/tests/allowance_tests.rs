@@ -0,0 +1,65 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup};
+use soroban_sdk::{testutils::AuthorizedFunction, Symbol};
+
+#[test]
+fn swap_from_allowance() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(150));
+    ctx.usdc_client
+        .approve(&ctx.trader, &ctx.broker, &amount(120), &1000);
+
+    ctx.broker_client.swap_from(
+        &ctx.usdc,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+    );
+
+    //trader authorized only the swap invocation, without nested token transfer
+    let (_, invocation) = ctx
+        .env
+        .auths()
+        .into_iter()
+        .find(|(address, _)| address == &ctx.trader)
+        .unwrap();
+    match invocation.function {
+        AuthorizedFunction::Contract((_, function, _)) => {
+            assert_eq!(function, Symbol::new(&ctx.env, "swap_from"))
+        }
+        _ => panic!("Unexpected invocation"),
+    }
+    assert!(invocation.sub_invocations.is_empty());
+
+    assert_eq!(
+        ctx.usdc_client.allowance(&ctx.trader, &ctx.broker),
+        amount(20)
+    );
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+    ctx.check_trader_balance(&ctx.usdc, amount(50));
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn swap_from_fail_insufficient_allowance() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    ctx.usdc_client
+        .approve(&ctx.trader, &ctx.broker, &amount(50), &1000);
+
+    //token contract rejects the transfer exceeding the allowance
+    ctx.broker_client.swap_from(
+        &ctx.usdc,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.path([]),
+        &None,
+    );
+}
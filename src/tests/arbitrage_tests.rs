@@ -0,0 +1,109 @@
+extern crate std;
+use crate::tests::mock_aqua_pool_contract::{MockAquaPoolContract, MockAquaPoolContractClient};
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{protocol::Protocol, route::Route, step::PathStep};
+use soroban_sdk::Vec;
+
+// USDC -> EURC path step through the pool with underpriced EURC
+fn cheap_eurc_step(ctx: &StrictSendTestContext) -> PathStep {
+    let pool = ctx.env.register(MockAquaPoolContract, ());
+    MockAquaPoolContractClient::new(&ctx.env, &pool).init(
+        &Vec::from_array(&ctx.env, [ctx.usdc.clone(), ctx.eurc.clone()]),
+        &Vec::from_array(
+            &ctx.env,
+            [amount(8000000) as u128, amount(10000000) as u128],
+        ),
+    );
+    ctx.usdc_asset_client.mint(&pool, &amount(8000000));
+    ctx.eurc_asset_client.mint(&pool, &amount(10000000));
+    PathStep {
+        protocol: Protocol::AquaConstant,
+        asset: ctx.eurc.clone(),
+        pool,
+        si: 0,
+        bi: 1,
+    }
+}
+
+fn cyclic_route(path: Vec<PathStep>) -> Route {
+    Route {
+        amount: amount(100),
+        min: 0,
+        estimated: 0,
+        alternatives: Vec::new(path.env()),
+        graph: Vec::new(path.env()),
+        path,
+    }
+}
+
+// USDC -> EURC -> USDC through the underpriced pool
+fn profitable_route(ctx: &StrictSendTestContext) -> Route {
+    cyclic_route(ctx.path([
+        cheap_eurc_step(ctx),
+        ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone()),
+    ]))
+}
+
+fn arbitrage(ctx: &StrictSendTestContext, route: Route, min_profit: i128) -> Vec<i128> {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    ctx.broker_client.arbitrage(
+        &ctx.usdc,
+        &Vec::from_array(&ctx.env, [route]),
+        &ctx.trader,
+        &min_profit,
+    )
+}
+
+#[test]
+fn arbitrage_profit_split() {
+    let ctx = setup();
+    ctx.broker_client.set_arbitrage_share(&2000);
+    let result = arbitrage(&ctx, profitable_route(&ctx), amount(10));
+    let profit = result.get(1).unwrap() - amount(100);
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    assert!(profit > amount(10));
+    //20% of the profit is kept by the treasury
+    let treasury_profit = profit * 2000 / 10000;
+    assert_eq!(result.get(3).unwrap(), treasury_profit);
+    assert_eq!(result.get(2).unwrap(), profit - treasury_profit);
+    ctx.check_trader_balance(&ctx.usdc, amount(100) + profit - treasury_profit);
+    ctx.check_contract_fee_balance(treasury_profit);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn arbitrage_without_treasury_share() {
+    let ctx = setup();
+    let result = arbitrage(&ctx, profitable_route(&ctx), 0);
+    assert_eq!(result.get(3).unwrap(), 0);
+    ctx.check_trader_balance(&ctx.usdc, result.get(1).unwrap());
+    ctx.check_contract_fee_balance(0);
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn arbitrage_min_profit_not_reached() {
+    let ctx = setup();
+    arbitrage(&ctx, profitable_route(&ctx), amount(1000));
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn arbitrage_loss() {
+    let ctx = setup();
+    //balanced pools yield less than the sold amount due to pool fees
+    let route = cyclic_route(ctx.path([
+        ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+        ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+        ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone()),
+    ]));
+    arbitrage(&ctx, route, 0);
+}
+
+#[test]
+#[should_panic(expected = "32711")]
+fn arbitrage_not_cyclic() {
+    let ctx = setup();
+    let route = cyclic_route(ctx.path([cheap_eurc_step(&ctx)]));
+    arbitrage(&ctx, route, 0);
+}
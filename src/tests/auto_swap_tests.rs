@@ -0,0 +1,149 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{protocol::Protocol, registeredpool::RegisteredPool};
+use soroban_sdk::{Address, Vec};
+
+fn pool(
+    ctx: &StrictSendTestContext,
+    protocol: Protocol,
+    pool: &Address,
+    tokens: [Address; 2],
+) -> RegisteredPool {
+    RegisteredPool {
+        protocol,
+        pool: pool.clone(),
+        tokens: Vec::from_array(&ctx.env, tokens),
+    }
+}
+
+// Register test context pools, optionally skipping the direct USDC/EURC pool
+fn register_pools(ctx: &StrictSendTestContext, direct: bool) {
+    let mut pools = Vec::from_array(
+        &ctx.env,
+        [
+            pool(
+                ctx,
+                Protocol::AquaConstant,
+                &ctx.usdc_xlm_pool,
+                [ctx.usdc.clone(), ctx.xlm.clone()],
+            ),
+            pool(
+                ctx,
+                Protocol::Soroswap,
+                &ctx.xlm_eurc_pool,
+                [ctx.xlm.clone(), ctx.eurc.clone()],
+            ),
+        ],
+    );
+    if direct {
+        pools.push_back(pool(
+            ctx,
+            Protocol::AquaConstant,
+            &ctx.usdc_eurc_pool,
+            [ctx.usdc.clone(), ctx.eurc.clone()],
+        ));
+    }
+    ctx.broker_client.set_pools(&pools);
+}
+
+fn swap_auto(ctx: &StrictSendTestContext, hops: u32) -> Vec<i128> {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    ctx.broker_client.swap_auto(
+        &ctx.usdc,
+        &ctx.eurc,
+        &amount(100),
+        &amount(70),
+        &ctx.trader,
+        &hops,
+        &None,
+    )
+}
+
+#[test]
+fn swap_auto_direct_pool() {
+    let ctx = setup();
+    register_pools(&ctx, true);
+    assert_eq!(ctx.broker_client.get_pools().len(), 3);
+    //direct pool yields more than the XLM path
+    let result = swap_auto(&ctx, 2);
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    assert_eq!(result.get(2).unwrap(), 0);
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, 830826408);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_auto_multi_hop() {
+    let ctx = setup();
+    register_pools(&ctx, false);
+    let result = swap_auto(&ctx, 3);
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, 828189411);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_auto_with_protocol_fees() {
+    let ctx = setup();
+    register_pools(&ctx, true);
+    ctx.broker_client
+        .set_protocol_fee(&Protocol::AquaConstant, &5);
+    ctx.broker_client.enable_protocol_fees(&true);
+    //fee is converted to USDC through the path found over registered pools
+    let result = swap_auto(&ctx, 2);
+    let fee = 830826408 * 5 / 1000;
+    ctx.check_trader_balance(&ctx.eurc, 830826408 - fee);
+    assert!(result.get(2).unwrap() > 0);
+    ctx.check_contract_fee_balance(result.get(2).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_auto_no_path() {
+    let ctx = setup();
+    register_pools(&ctx, false);
+    //path through XLM requires two hops
+    swap_auto(&ctx, 1);
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_auto_too_many_hops() {
+    let ctx = setup();
+    register_pools(&ctx, true);
+    swap_auto(&ctx, 4);
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn set_pools_duplicate_tokens() {
+    let ctx = setup();
+    ctx.broker_client.set_pools(&Vec::from_array(
+        &ctx.env,
+        [pool(
+            &ctx,
+            Protocol::AquaConstant,
+            &ctx.usdc_xlm_pool,
+            [ctx.usdc.clone(), ctx.usdc.clone()],
+        )],
+    ));
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn set_pools_too_many() {
+    let ctx = setup();
+    let mut pools = Vec::new(&ctx.env);
+    for _ in 0..=crate::pathfinder::MAX_POOLS {
+        pools.push_back(pool(
+            &ctx,
+            Protocol::AquaConstant,
+            &ctx.usdc_xlm_pool,
+            [ctx.usdc.clone(), ctx.xlm.clone()],
+        ));
+    }
+    ctx.broker_client.set_pools(&pools);
+}
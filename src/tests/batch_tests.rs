@@ -0,0 +1,85 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{route::Route, swaprequest::SwapRequest};
+use soroban_sdk::{testutils::Address as _, Address, Vec};
+
+fn xlm_eurc_request(ctx: &StrictSendTestContext, trader: &Address) -> SwapRequest {
+    ctx.xlm_asset_client.mint(trader, &amount(100));
+    SwapRequest {
+        selling: ctx.xlm.clone(),
+        routes: Vec::from_array(
+            &ctx.env,
+            [Route {
+                amount: amount(100),
+                min: 1,
+                estimated: 1,
+                path: ctx.path([ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            }],
+        ),
+        trader: trader.clone(),
+        vfee: 0,
+        ffee: 10,
+        fpath: ctx.fee_path(),
+        partner: None,
+    }
+}
+
+#[test]
+fn swap_batch() {
+    let ctx = setup();
+    let other_trader = Address::generate(&ctx.env);
+    let first = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let second = xlm_eurc_request(&ctx, &other_trader);
+
+    let results = ctx
+        .broker_client
+        .swap_batch(&Vec::from_array(&ctx.env, [first, second]));
+
+    //each trader authorized only its own request
+    let auths = ctx.env.auths();
+    assert_eq!(auths.len(), 2);
+    assert_eq!(auths[0].0, ctx.trader);
+    assert_eq!(auths[1].0, other_trader);
+
+    let first_result = results.get(0).unwrap();
+    assert_eq!(first_result.get(2).unwrap(), 32826388);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+    ctx.check_trader_balance(&ctx.usdc, 0);
+
+    let second_result = results.get(1).unwrap();
+    assert_eq!(second_result.get(0).unwrap(), amount(100));
+    assert_eq!(
+        ctx.eurc_client.balance(&other_trader),
+        second_result.get(1).unwrap()
+    );
+    assert_eq!(ctx.xlm_client.balance(&other_trader), 0);
+    ctx.check_contract_fee_balance(first_result.get(2).unwrap() + second_result.get(2).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_batch_reverted() {
+    let ctx = setup();
+    let other_trader = Address::generate(&ctx.env);
+    let first = xlm_eurc_request(&ctx, &other_trader);
+    //unfeasible min amount
+    let second = ctx.usdc_eurc_request(&ctx.trader, amount(90));
+
+    let result = ctx
+        .broker_client
+        .try_swap_batch(&Vec::from_array(&ctx.env, [first, second]));
+    assert!(result.is_err());
+    assert_eq!(ctx.xlm_client.balance(&other_trader), amount(100));
+    assert_eq!(ctx.eurc_client.balance(&other_trader), 0);
+    ctx.check_trader_balance(&ctx.usdc, amount(100));
+    ctx.check_contract_fee_balance(0);
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_batch_fail_empty() {
+    let ctx = setup();
+    ctx.broker_client.swap_batch(&Vec::new(&ctx.env));
+}
@@ -0,0 +1,158 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{protocol::Protocol, route::Route, step::PathStep};
+use soroban_sdk::Vec;
+
+fn route(amount: i128, min: i128, path: Vec<PathStep>) -> Route {
+    Route {
+        amount,
+        min,
+        estimated: min,
+        alternatives: Vec::new(path.env()),
+        graph: Vec::new(path.env()),
+        path,
+    }
+}
+
+fn swap_best_effort(ctx: &StrictSendTestContext, routes: Vec<Route>) -> (Vec<i128>, Vec<u32>) {
+    ctx.broker_client.swap_best_effort(
+        &ctx.usdc,
+        &routes,
+        &ctx.trader,
+        &0,
+        &10,
+        &ctx.fee_path(),
+        &None,
+    )
+}
+
+#[test]
+fn swap_best_effort_skip_failed_routes() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [
+            route(
+                amount(70),
+                amount(45),
+                ctx.path([
+                    ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                    ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+                ]),
+            ),
+            //first step fails
+            route(
+                amount(30),
+                amount(20),
+                ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+            ),
+        ],
+    );
+
+    let (result, failed) = swap_best_effort(&ctx, routes);
+
+    assert_eq!(failed, Vec::from_array(&ctx.env, [1u32]));
+    assert_eq!(result.get(0).unwrap(), amount(70));
+    ctx.check_trader_balance(&ctx.usdc, amount(30));
+    ctx.check_trader_balance(&ctx.xlm, 0);
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    ctx.check_contract_fee_balance(result.get(2).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_best_effort_fail_after_first_step() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    //second step fails, bought intermediate tokens can't be refunded
+    let routes = Vec::from_array(
+        &ctx.env,
+        [
+            route(
+                amount(80),
+                amount(50),
+                ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())]),
+            ),
+            route(
+                amount(20),
+                amount(10),
+                ctx.path([
+                    ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                    ctx.broken_step(ctx.eurc.clone()),
+                ]),
+            ),
+        ],
+    );
+
+    swap_best_effort(&ctx, routes);
+}
+
+#[test]
+fn swap_best_effort_disabled_protocol() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let mut disabled_step = ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone());
+    disabled_step.protocol = Protocol::Phoenix;
+    let routes = Vec::from_array(
+        &ctx.env,
+        [
+            route(
+                amount(60),
+                amount(40),
+                ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())]),
+            ),
+            route(amount(40), amount(30), ctx.path([disabled_step])),
+        ],
+    );
+
+    let (result, failed) = swap_best_effort(&ctx, routes);
+
+    assert_eq!(failed, Vec::from_array(&ctx.env, [1u32]));
+    assert_eq!(result.get(0).unwrap(), amount(60));
+    ctx.check_trader_balance(&ctx.usdc, amount(40));
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_best_effort_fail_scaled_min() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    //scaled min = (1 + 100) / 2 EURC
+    let routes = Vec::from_array(
+        &ctx.env,
+        [
+            route(
+                amount(50),
+                1,
+                ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())]),
+            ),
+            route(
+                amount(50),
+                amount(100),
+                ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+            ),
+        ],
+    );
+
+    swap_best_effort(&ctx, routes);
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_best_effort_fail_all_routes() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [route(
+            amount(100),
+            1,
+            ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+        )],
+    );
+
+    swap_best_effort(&ctx, routes);
+}
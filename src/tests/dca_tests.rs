@@ -0,0 +1,196 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::dcaorder::DcaOrder;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+fn usdc_eurc_dca(ctx: &StrictSendTestContext, price: i128) -> DcaOrder {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    DcaOrder {
+        trader: ctx.trader.clone(),
+        selling: ctx.usdc.clone(),
+        buying: ctx.eurc.clone(),
+        amount: amount(100),
+        slice: amount(40),
+        period: 100,
+        price,
+        vfee: 0,
+        ffee: 10,
+    }
+}
+
+#[test]
+fn execute_dca_order() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    ctx.broker_client.set_keeper_reward(&10);
+
+    let order_id = ctx
+        .broker_client
+        .place_dca_order(&usdc_eurc_dca(&ctx, 5_000_000));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    assert_eq!(ctx.usdc_client.balance(&ctx.broker), amount(100));
+
+    let mut total_received = 0;
+    let mut total_reward = 0;
+    for (i, slice) in [amount(40), amount(40), amount(20)].iter().enumerate() {
+        ctx.env.ledger().set_sequence_number(i as u32 * 100);
+        let result = ctx.broker_client.execute_dca_order(
+            &order_id,
+            &ctx.usdc_eurc_routes(*slice, 1),
+            &ctx.fee_path(),
+            &keeper,
+        );
+        assert_eq!(result.get(0).unwrap(), *slice);
+        total_received += result.get(1).unwrap();
+        total_reward += result.get(3).unwrap();
+
+        //status is kept until the last slice
+        if i < 2 {
+            let status = ctx.broker_client.get_dca_status(&order_id);
+            assert_eq!(status.executions, i as u32 + 1);
+            assert_eq!(status.bought, total_received);
+            assert_eq!(status.next, i as u32 * 100 + 100);
+        }
+    }
+
+    //completed order is removed
+    assert!(ctx.broker_client.try_get_dca_order(&order_id).is_err());
+    assert!(ctx.broker_client.try_get_dca_status(&order_id).is_err());
+    ctx.check_trader_balance(&ctx.eurc, total_received);
+    assert_eq!(ctx.eurc_client.balance(&keeper), total_reward);
+    ctx.check_no_interim_leftovers();
+    //completed order can't be executed anymore
+    ctx.env.ledger().set_sequence_number(1000);
+    assert!(ctx
+        .broker_client
+        .try_execute_dca_order(
+            &order_id,
+            &ctx.usdc_eurc_routes(amount(20), 1),
+            &ctx.fee_path(),
+            &keeper
+        )
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "32742")]
+fn execute_dca_order_fail_not_ready() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_dca_order(&usdc_eurc_dca(&ctx, 5_000_000));
+
+    ctx.broker_client.execute_dca_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(40), 1),
+        &ctx.fee_path(),
+        &keeper,
+    );
+    ctx.env.ledger().set_sequence_number(99);
+    ctx.broker_client.execute_dca_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(40), 1),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn execute_dca_order_fail_slice_amount() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_dca_order(&usdc_eurc_dca(&ctx, 5_000_000));
+
+    ctx.broker_client.execute_dca_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), 1),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn execute_dca_order_fail_price() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_dca_order(&usdc_eurc_dca(&ctx, 9_000_000));
+
+    ctx.broker_client.execute_dca_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(40), 1),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32732")]
+fn place_dca_order_fail_signed_only() {
+    let ctx = setup();
+    ctx.broker_client.require_signed_quotes(&true);
+    ctx.broker_client
+        .place_dca_order(&usdc_eurc_dca(&ctx, 5_000_000));
+}
+
+#[test]
+#[should_panic(expected = "32732")]
+fn execute_dca_order_fail_signed_only() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_dca_order(&usdc_eurc_dca(&ctx, 5_000_000));
+    //order placed before signed quotes were required
+    ctx.broker_client.require_signed_quotes(&true);
+
+    ctx.broker_client.execute_dca_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(40), 1),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+fn cancel_dca_order() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_dca_order(&usdc_eurc_dca(&ctx, 5_000_000));
+    let result = ctx.broker_client.execute_dca_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(40), 1),
+        &ctx.fee_path(),
+        &keeper,
+    );
+
+    let refunded = ctx.broker_client.cancel_dca_order(&order_id);
+    assert_eq!(refunded, amount(60));
+    ctx.check_trader_balance(&ctx.usdc, amount(60));
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    //cancelled order is removed
+    assert!(ctx.broker_client.try_get_dca_order(&order_id).is_err());
+    assert!(ctx.broker_client.try_get_dca_status(&order_id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn place_dca_order_fail_zero_period() {
+    let ctx = setup();
+    let order = DcaOrder {
+        period: 0,
+        ..usdc_eurc_dca(&ctx, 5_000_000)
+    };
+    ctx.broker_client.place_dca_order(&order);
+}
@@ -0,0 +1,34 @@
+extern crate std;
+use crate::tests::swap_test_context::setup;
+
+#[test]
+fn swap_exempt_trader() {
+    let ctx = setup();
+    ctx.broker_client.set_trader_exempt(&ctx.trader, &true);
+
+    let result = ctx.swap_usdc_eurc();
+    assert_eq!(result.get(2).unwrap(), 0);
+    ctx.check_contract_fee_balance(0);
+    ctx.check_trader_balance(&ctx.eurc, 828189411);
+    ctx.check_no_interim_leftovers();
+
+    //revoked exemption
+    ctx.broker_client.set_trader_exempt(&ctx.trader, &false);
+    let result = ctx.swap_usdc_eurc();
+    assert!(result.get(2).unwrap() > 0);
+}
+
+#[test]
+fn swap_exempt_pair() {
+    let ctx = setup();
+    //exemption applies only to the specified direction
+    ctx.broker_client
+        .set_pair_exempt(&ctx.eurc, &ctx.usdc, &true);
+    let result = ctx.swap_usdc_eurc();
+    assert_eq!(result.get(2).unwrap(), 32826388);
+
+    ctx.broker_client
+        .set_pair_exempt(&ctx.usdc, &ctx.eurc, &true);
+    let result = ctx.swap_usdc_eurc();
+    assert_eq!(result.get(2).unwrap(), 0);
+}
@@ -0,0 +1,87 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup};
+use crate::types::{feetier::FeeTier, route::Route};
+use soroban_sdk::Vec;
+
+#[test]
+fn swap_with_volume_discount() {
+    let ctx = setup();
+    ctx.broker_client.set_fee_tiers(&Vec::from_array(
+        &ctx.env,
+        [
+            FeeTier {
+                volume: amount(100),
+                discount: 5000,
+            },
+            FeeTier {
+                volume: amount(1000),
+                discount: 10_000,
+            },
+        ],
+    ));
+
+    //no discount for the first swap
+    let result = ctx.swap_usdc_eurc();
+    assert_eq!(result.get(2).unwrap(), 32826388);
+    //selling token is the fee token
+    assert_eq!(ctx.broker_client.trader_volume(&ctx.trader), amount(100));
+
+    //second swap gets 50% discount
+    let result = ctx.swap_usdc_eurc();
+    assert_eq!(result.get(2).unwrap(), 16391094);
+    assert_eq!(ctx.broker_client.trader_volume(&ctx.trader), amount(200));
+}
+
+#[test]
+fn track_volume_with_fee_conversion() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.xlm, amount(1000));
+
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(730),
+            min: amount(58),
+            estimated: amount(60),
+            path: ctx.path([
+                ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone()),
+                ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone()),
+            ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
+        }],
+    );
+    ctx.broker_client.swap(
+        &ctx.xlm,
+        &routes,
+        &ctx.trader,
+        &300,
+        &0,
+        &ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone())]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    );
+
+    //bought EURC amount converted at the fee conversion rate
+    assert_eq!(ctx.broker_client.trader_volume(&ctx.trader), 723400865);
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn set_fee_tiers_fail_unsorted() {
+    let ctx = setup();
+    ctx.broker_client.set_fee_tiers(&Vec::from_array(
+        &ctx.env,
+        [
+            FeeTier {
+                volume: amount(1000),
+                discount: 1000,
+            },
+            FeeTier {
+                volume: amount(100),
+                discount: 2000,
+            },
+        ],
+    ));
+}
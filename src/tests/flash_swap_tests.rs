@@ -0,0 +1,150 @@
+extern crate std;
+use crate::tests::mock_flash_receiver_contract::{
+    MockFlashReceiverContract, MockFlashReceiverContractClient,
+};
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{flashconfig::FlashConfig, route::Route};
+use soroban_sdk::{Address, Bytes, Vec};
+
+// Deploy the receiver delivering given amount to the broker in the callback
+fn receiver(ctx: &StrictSendTestContext, repay: i128) -> Address {
+    let receiver = ctx.env.register(MockFlashReceiverContract, ());
+    MockFlashReceiverContractClient::new(&ctx.env, &receiver).init(&ctx.broker, &repay);
+    ctx.usdc_asset_client.mint(&receiver, &repay);
+    receiver
+}
+
+// Enable flash swaps lending up to 50% of the unreserved balance with 0.3% flash fee
+fn enable_flash(ctx: &StrictSendTestContext) {
+    ctx.broker_client.set_flash_config(&FlashConfig {
+        enabled: true,
+        max_share: 5_000,
+        fee: 30,
+    });
+}
+
+fn flash_swap(ctx: &StrictSendTestContext, receiver: &Address) -> Vec<i128> {
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(100),
+            min: amount(70),
+            estimated: amount(70),
+            path: ctx.path([
+                ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+            ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
+        }],
+    );
+    ctx.broker_client.flash_swap(
+        &ctx.usdc,
+        &routes,
+        &ctx.trader,
+        receiver,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+        &Bytes::from_array(&ctx.env, &[1, 2, 3]),
+    )
+}
+
+#[test]
+fn flash_swap_repaid() {
+    let ctx = setup();
+    enable_flash(&ctx);
+    //contract treasury funds the swap
+    ctx.usdc_asset_client.mint(&ctx.broker, &amount(1000));
+    //sold amount plus 0.3% flash fee
+    let receiver = receiver(&ctx, amount(100) + 3_000_000);
+    let result = flash_swap(&ctx, &receiver);
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    assert_eq!(result.get(1).unwrap(), 800679106);
+    assert_eq!(ctx.eurc_client.balance(&receiver), 800679106);
+    assert_eq!(ctx.usdc_client.balance(&receiver), 0);
+    //treasury balance is restored and both the swap fee and the flash fee are credited
+    ctx.check_contract_fee_balance(amount(1000) + 32826388 + 3_000_000);
+    ctx.check_no_interim_leftovers();
+    let (initiator, sold, data) =
+        MockFlashReceiverContractClient::new(&ctx.env, &receiver).callback_data();
+    assert_eq!(initiator, ctx.trader);
+    assert_eq!(sold, amount(100));
+    assert_eq!(data, Bytes::from_array(&ctx.env, &[1, 2, 3]));
+}
+
+#[test]
+#[should_panic(expected = "32714")]
+fn flash_swap_not_repaid() {
+    let ctx = setup();
+    enable_flash(&ctx);
+    ctx.usdc_asset_client.mint(&ctx.broker, &amount(1000));
+    let receiver = receiver(&ctx, amount(99));
+    flash_swap(&ctx, &receiver);
+}
+
+#[test]
+#[should_panic(expected = "32702")]
+fn flash_swap_insufficient_liquidity() {
+    let ctx = setup();
+    enable_flash(&ctx);
+    ctx.usdc_asset_client.mint(&ctx.broker, &amount(50));
+    let receiver = receiver(&ctx, amount(100));
+    flash_swap(&ctx, &receiver);
+}
+
+#[test]
+#[should_panic(expected = "32702")]
+fn flash_swap_liquidity_cap() {
+    let ctx = setup();
+    enable_flash(&ctx);
+    //only half of the balance can be lent
+    ctx.usdc_asset_client.mint(&ctx.broker, &amount(150));
+    let receiver = receiver(&ctx, amount(101));
+    flash_swap(&ctx, &receiver);
+}
+
+#[test]
+#[should_panic(expected = "32714")]
+fn flash_swap_fee_not_paid() {
+    let ctx = setup();
+    enable_flash(&ctx);
+    ctx.usdc_asset_client.mint(&ctx.broker, &amount(1000));
+    let receiver = receiver(&ctx, amount(100));
+    flash_swap(&ctx, &receiver);
+}
+
+#[test]
+#[should_panic(expected = "32715")]
+fn flash_swap_not_configured() {
+    let ctx = setup();
+    ctx.usdc_asset_client.mint(&ctx.broker, &amount(1000));
+    let receiver = receiver(&ctx, amount(101));
+    flash_swap(&ctx, &receiver);
+}
+
+#[test]
+#[should_panic(expected = "32715")]
+fn flash_swap_disabled() {
+    let ctx = setup();
+    ctx.broker_client.set_flash_config(&FlashConfig {
+        enabled: false,
+        max_share: 5_000,
+        fee: 30,
+    });
+    ctx.usdc_asset_client.mint(&ctx.broker, &amount(1000));
+    let receiver = receiver(&ctx, amount(101));
+    flash_swap(&ctx, &receiver);
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn flash_config_zero_fee() {
+    let ctx = setup();
+    ctx.broker_client.set_flash_config(&FlashConfig {
+        enabled: true,
+        max_share: 5_000,
+        fee: 0,
+    });
+}
@@ -0,0 +1,227 @@
+extern crate std;
+use crate::tests::mock_aqua_pool_contract::{MockAquaPoolContract, MockAquaPoolContractClient};
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{graphstep::GraphStep, protocol::Protocol, route::Route, step::PathStep};
+use soroban_sdk::Vec;
+
+// XLM -> EURC path step through the second XLM/EURC pool
+fn second_xlm_eurc_step(ctx: &StrictSendTestContext) -> PathStep {
+    let pool = ctx.env.register(MockAquaPoolContract, ());
+    MockAquaPoolContractClient::new(&ctx.env, &pool).init(
+        &Vec::from_array(&ctx.env, [ctx.xlm.clone(), ctx.eurc.clone()]),
+        &Vec::from_array(
+            &ctx.env,
+            [amount(12000000) as u128, amount(1000000) as u128],
+        ),
+    );
+    ctx.xlm_asset_client.mint(&pool, &amount(12000000));
+    ctx.eurc_asset_client.mint(&pool, &amount(1000000));
+    PathStep {
+        protocol: Protocol::AquaConstant,
+        asset: ctx.eurc.clone(),
+        pool,
+        si: 0,
+        bi: 1,
+    }
+}
+
+fn edge(from: u32, to: u32, share: u32, step: PathStep) -> GraphStep {
+    GraphStep {
+        from,
+        to,
+        share,
+        step,
+    }
+}
+
+fn swap_graph<const N: usize>(
+    ctx: &StrictSendTestContext,
+    min: i128,
+    graph: [GraphStep; N],
+) -> Vec<i128> {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(100),
+            min,
+            estimated: min,
+            path: Vec::new(&ctx.env),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::from_array(&ctx.env, graph),
+        }],
+    );
+    ctx.broker_client.swap(
+        &ctx.usdc,
+        &routes,
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    )
+}
+
+#[test]
+fn swap_graph_chain() {
+    let ctx = setup();
+    //graph without splits is equivalent to the linear path
+    let result = swap_graph(
+        &ctx,
+        amount(70),
+        [
+            edge(0, 1, 10000, ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())),
+            edge(1, 2, 10000, ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())),
+        ],
+    );
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_graph_split_after_first_hop() {
+    let ctx = setup();
+    //USDC -> XLM, then XLM split across two XLM -> EURC pools
+    let result = swap_graph(
+        &ctx,
+        amount(70),
+        [
+            edge(0, 1, 10000, ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())),
+            edge(1, 2, 6000, ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())),
+            edge(1, 2, 4000, second_xlm_eurc_step(&ctx)),
+        ],
+    );
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    assert!(result.get(1).unwrap() > amount(70));
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_graph_split_and_merge() {
+    let ctx = setup();
+    //USDC split between direct pool and XLM hop, merged back into EURC
+    let result = swap_graph(
+        &ctx,
+        amount(70),
+        [
+            edge(0, 1, 6000, ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())),
+            edge(0, 2, 4000, ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())),
+            edge(1, 2, 10000, ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())),
+        ],
+    );
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    //merged amount lies between outputs of the direct pool and the XLM path
+    assert!(result.get(1).unwrap() > 800679106);
+    assert!(result.get(1).unwrap() < 802894183);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32711")]
+fn swap_graph_incomplete_split() {
+    let ctx = setup();
+    //only 90% of XLM is routed further
+    swap_graph(
+        &ctx,
+        amount(70),
+        [
+            edge(0, 1, 10000, ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())),
+            edge(1, 2, 6000, ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())),
+            edge(1, 2, 3000, second_xlm_eurc_step(&ctx)),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "32711")]
+fn swap_graph_split_before_merge() {
+    let ctx = setup();
+    //XLM node is spent before the second incoming step is executed
+    swap_graph(
+        &ctx,
+        amount(70),
+        [
+            edge(0, 1, 5000, ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())),
+            edge(1, 2, 10000, ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())),
+            edge(0, 1, 5000, ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "32711")]
+fn swap_graph_merge_different_tokens() {
+    let ctx = setup();
+    swap_graph(
+        &ctx,
+        amount(70),
+        [
+            edge(0, 1, 5000, ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())),
+            edge(0, 1, 5000, ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())),
+        ],
+    );
+}
+
+// XLM split between the Soroswap XLM -> EURC -> USDC hops and the direct Aqua pool
+fn swap_graph_xlm_usdc(ctx: &StrictSendTestContext) -> Vec<i128> {
+    ctx.fund_trader(&ctx.xlm, amount(1000));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(1000),
+            min: amount(50),
+            estimated: amount(50),
+            path: Vec::new(&ctx.env),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::from_array(
+                &ctx.env,
+                [
+                    edge(0, 1, 6000, ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())),
+                    edge(0, 2, 4000, ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone())),
+                    edge(1, 2, 10000, ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone())),
+                ],
+            ),
+        }],
+    );
+    ctx.broker_client.swap(
+        &ctx.xlm,
+        &routes,
+        &ctx.trader,
+        &0,
+        &0,
+        &Vec::new(&ctx.env),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    )
+}
+
+#[test]
+fn swap_graph_with_protocol_fees() {
+    //bought amount without fees
+    let ctx = setup();
+    let bought = swap_graph_xlm_usdc(&ctx).get(1).unwrap();
+
+    let ctx = setup();
+    ctx.broker_client
+        .set_protocol_fee(&Protocol::AquaConstant, &5);
+    ctx.broker_client.set_protocol_fee(&Protocol::Soroswap, &10);
+    ctx.broker_client.enable_protocol_fees(&true);
+    let result = swap_graph_xlm_usdc(&ctx);
+
+    //Aqua fee is charged from the whole volume, Soroswap fee only from the 60% routed through it
+    let fee = bought * 5 / 1000 + (bought * 6 / 10) * 10 / 1000;
+    assert_eq!(result.get(1).unwrap(), bought - fee);
+    assert_eq!(result.get(2).unwrap(), fee);
+    ctx.check_contract_fee_balance(fee);
+    ctx.check_no_interim_leftovers();
+}
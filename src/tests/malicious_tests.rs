@@ -74,6 +74,8 @@ fn attempt_intermediate_asset_fee_drain() {
                     },
                 ],
             ),
+            alternatives: Vec::new(&env),
+            graph: Vec::new(&env),
         }],
     );
 
@@ -85,5 +87,8 @@ fn attempt_intermediate_asset_fee_drain() {
         &0,
         &0,
         &Vec::from_array(&env, []),
+        &None,
+        &None,
+        &Vec::new(&env),
     );
 }
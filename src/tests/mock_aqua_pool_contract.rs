@@ -29,7 +29,7 @@ pub fn get_aqua_amount_out(
         &(reserves.1 as u128),
         &((reserves.0 + amount_in) as u128),
     );
-    let fee = result.fixed_mul_ceil(&env, &(fee as u128), &(fee_multiplier as u128));
+    let fee = result.fixed_mul_ceil(env, &(fee as u128), &(fee_multiplier as u128));
     (result - fee) as i128
 }
 
@@ -50,6 +50,10 @@ impl MockAquaPoolContract {
         e.storage().instance().get(&"reserves").unwrap()
     }
 
+    pub fn get_fee_fraction(_e: Env) -> u32 {
+        FEE as u32
+    }
+
     pub fn swap(
         e: Env,
         user: Address,
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+use soroban_sdk::{contract, contractimpl, token::TokenClient, Address, Bytes, Env};
+
+#[contract]
+pub struct MockFlashReceiverContract;
+
+#[contractimpl]
+impl MockFlashReceiverContract {
+    pub fn init(e: Env, broker: Address, repay: i128) {
+        e.storage().instance().set(&"broker", &broker);
+        e.storage().instance().set(&"repay", &repay);
+    }
+
+    pub fn on_flash_swap(
+        e: Env,
+        initiator: Address,
+        selling: Address,
+        amount: i128,
+        buying: Address,
+        bought: i128,
+        data: Bytes,
+    ) {
+        //bought tokens should be already received
+        let balance = TokenClient::new(&e, &buying).balance(&e.current_contract_address());
+        assert!(balance >= bought);
+        e.storage().instance().set(&"initiator", &initiator);
+        e.storage().instance().set(&"amount", &amount);
+        e.storage().instance().set(&"data", &data);
+        //deliver selling tokens back to the broker
+        let broker: Address = e.storage().instance().get(&"broker").unwrap();
+        let repay: i128 = e.storage().instance().get(&"repay").unwrap();
+        if repay > 0 {
+            TokenClient::new(&e, &selling).transfer(&e.current_contract_address(), &broker, &repay);
+        }
+    }
+
+    pub fn callback_data(e: Env) -> (Address, i128, Bytes) {
+        (
+            e.storage().instance().get(&"initiator").unwrap(),
+            e.storage().instance().get(&"amount").unwrap(),
+            e.storage().instance().get(&"data").unwrap(),
+        )
+    }
+}
@@ -34,7 +34,7 @@ impl MaliciousLPContract {
             &token_in,
             &user,
             token_amount_in,
-            token_amount_in.clone(),
+            token_amount_in,
         );
 
         // Retrieve the last token address stored during initialization
@@ -63,10 +63,10 @@ impl MaliciousLPContract {
 // Transfers the Specific Token from the User’s Address to the Contract’s Address
 pub fn pull_underlying(e: &Env, token: &Address, from: &Address, amount: i128, max_amount: i128) {
     let ledger = (e.ledger().sequence() / 100000 + 1) * 100000;
-    TokenClient::new(e, token).approve(&from, &e.current_contract_address(), &max_amount, &ledger);
+    TokenClient::new(e, token).approve(from, &e.current_contract_address(), &max_amount, &ledger);
     TokenClient::new(e, token).transfer_from(
         &e.current_contract_address(),
-        &from,
+        from,
         &e.current_contract_address(),
         &amount,
     );
@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+use soroban_sdk::{contract, contractimpl, Env};
+
+use crate::types::oracle::{Asset, PriceData};
+
+// Oracle price of one base asset unit (14 decimals)
+pub const ORACLE_UNIT: i128 = 100_000_000_000_000;
+
+#[contract]
+pub struct MockOracleContract;
+
+#[contractimpl]
+impl MockOracleContract {
+    pub fn decimals(_e: Env) -> u32 {
+        14
+    }
+
+    pub fn lastprice(e: Env, asset: Asset) -> Option<PriceData> {
+        e.storage().instance().get(&asset)
+    }
+
+    pub fn set_price(e: Env, asset: Asset, price: i128, timestamp: u64) {
+        e.storage()
+            .instance()
+            .set(&asset, &PriceData { price, timestamp });
+    }
+}
@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+use soroban_sdk::{contract, contractimpl, token::TokenClient, Address, Bytes, Env};
+
+#[contract]
+pub struct MockSwapHookContract;
+
+#[contractimpl]
+impl MockSwapHookContract {
+    pub fn on_swap(e: Env, trader: Address, token: Address, amount: i128, payload: Bytes) {
+        //empty payload rejects the swap
+        if payload.is_empty() {
+            panic!("empty payload");
+        }
+        //bought tokens should be already received
+        let balance = TokenClient::new(&e, &token).balance(&e.current_contract_address());
+        assert!(balance >= amount);
+        e.storage()
+            .instance()
+            .set(&"last", &(trader, token, amount, payload));
+    }
+
+    pub fn last_swap(e: Env) -> (Address, Address, i128, Bytes) {
+        e.storage().instance().get(&"last").unwrap()
+    }
+}
@@ -1,8 +1,36 @@
 #![cfg(test)]
+mod allowance_tests;
+mod arbitrage_tests;
+mod auto_swap_tests;
+mod batch_tests;
+mod best_effort_tests;
+mod dca_tests;
+mod fee_exemption_tests;
+mod fee_tier_tests;
+mod flash_swap_tests;
+mod graph_route_tests;
 pub mod malicious_tests;
 mod mock_aqua_pool_contract;
+mod mock_flash_receiver_contract;
 mod mock_soroswap_pair_contract;
 mod mock_malicious_lp_contract;
+mod mock_oracle_contract;
+mod mock_swap_hook_contract;
+mod multi_input_tests;
+mod multi_output_tests;
+mod optimized_swap_tests;
+mod order_tests;
+mod partner_tests;
+mod payment_tests;
+mod payout_split_tests;
+mod price_check_tests;
+mod protocol_fee_tests;
+mod rebalance_tests;
+mod relayer_tests;
+mod route_alternatives_tests;
+mod signed_quote_tests;
 mod strict_send_tests;
+mod swap_hook_tests;
 mod soroswap_adapter_tests;
-mod swap_test_context;
\ No newline at end of file
+mod swap_test_context;
+mod trigger_order_tests;
\ No newline at end of file
@@ -0,0 +1,140 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{route::Route, swapinput::SwapInput};
+use soroban_sdk::Vec;
+
+fn usdc_input(ctx: &StrictSendTestContext) -> SwapInput {
+    SwapInput {
+        selling: ctx.usdc.clone(),
+        routes: Vec::from_array(
+            &ctx.env,
+            [Route {
+                amount: amount(100),
+                min: amount(70),
+                estimated: amount(70),
+                path: ctx.path([
+                    ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                    ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+                ]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            }],
+        ),
+    }
+}
+
+fn xlm_input(ctx: &StrictSendTestContext) -> SwapInput {
+    SwapInput {
+        selling: ctx.xlm.clone(),
+        routes: Vec::from_array(
+            &ctx.env,
+            [Route {
+                amount: amount(50),
+                min: 1,
+                estimated: 1,
+                path: ctx.path([ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone())]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            }],
+        ),
+    }
+}
+
+#[test]
+fn swap_multi_input() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    ctx.fund_trader(&ctx.xlm, amount(50));
+
+    let results = ctx.broker_client.swap_multi_input(
+        &Vec::from_array(&ctx.env, [usdc_input(&ctx), xlm_input(&ctx)]),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &amount(80),
+        &None,
+    );
+
+    let usdc_result = results.get(0).unwrap();
+    let xlm_result = results.get(1).unwrap();
+    //first input matches the single swap outcome
+    assert_eq!(usdc_result.get(1).unwrap(), 800679106);
+    assert_eq!(usdc_result.get(2).unwrap(), 32826388);
+    assert_eq!(xlm_result.get(0).unwrap(), amount(50));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.xlm, 0);
+    ctx.check_trader_balance(
+        &ctx.eurc,
+        usdc_result.get(1).unwrap() + xlm_result.get(1).unwrap(),
+    );
+    ctx.check_contract_fee_balance(usdc_result.get(2).unwrap() + xlm_result.get(2).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_multi_input_fail_total_min() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    ctx.fund_trader(&ctx.xlm, amount(50));
+
+    ctx.broker_client.swap_multi_input(
+        &Vec::from_array(&ctx.env, [usdc_input(&ctx), xlm_input(&ctx)]),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &amount(150),
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_multi_input_fail_duplicate() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.xlm, amount(100));
+
+    ctx.broker_client.swap_multi_input(
+        &Vec::from_array(&ctx.env, [xlm_input(&ctx), xlm_input(&ctx)]),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &0,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_multi_input_fail_different_outputs() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    ctx.fund_trader(&ctx.xlm, amount(50));
+    let xlm_usdc_input = SwapInput {
+        selling: ctx.xlm.clone(),
+        routes: Vec::from_array(
+            &ctx.env,
+            [Route {
+                amount: amount(50),
+                min: 1,
+                estimated: 1,
+                path: ctx.path([ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone())]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            }],
+        ),
+    };
+
+    ctx.broker_client.swap_multi_input(
+        &Vec::from_array(&ctx.env, [usdc_input(&ctx), xlm_usdc_input]),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &0,
+        &None,
+    );
+}
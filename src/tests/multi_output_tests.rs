@@ -0,0 +1,100 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{route::Route, swapoutput::SwapOutput};
+use soroban_sdk::Vec;
+
+fn eurc_output(ctx: &StrictSendTestContext, min: i128) -> SwapOutput {
+    SwapOutput {
+        routes: Vec::from_array(
+            &ctx.env,
+            [Route {
+                amount: amount(60),
+                min: 1,
+                estimated: 1,
+                path: ctx.path([
+                    ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                    ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+                ]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            }],
+        ),
+        min,
+        fpath: ctx.fee_path(),
+    }
+}
+
+fn xlm_output(ctx: &StrictSendTestContext) -> SwapOutput {
+    SwapOutput {
+        routes: Vec::from_array(
+            &ctx.env,
+            [Route {
+                amount: amount(40),
+                min: 1,
+                estimated: 1,
+                path: ctx.path([ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            }],
+        ),
+        min: 0,
+        fpath: ctx.path([ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone())]),
+    }
+}
+
+#[test]
+fn swap_multi_output() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+
+    let results = ctx.broker_client.swap_multi_output(
+        &ctx.usdc,
+        &Vec::from_array(&ctx.env, [eurc_output(&ctx, 1), xlm_output(&ctx)]),
+        &ctx.trader,
+        &0,
+        &10,
+        &None,
+    );
+
+    let eurc_result = results.get(0).unwrap();
+    let xlm_result = results.get(1).unwrap();
+    assert_eq!(eurc_result.get(0).unwrap(), amount(60));
+    assert_eq!(xlm_result.get(0).unwrap(), amount(40));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, eurc_result.get(1).unwrap());
+    ctx.check_trader_balance(&ctx.xlm, xlm_result.get(1).unwrap());
+    ctx.check_contract_fee_balance(eurc_result.get(2).unwrap() + xlm_result.get(2).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_multi_output_fail_min() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+
+    ctx.broker_client.swap_multi_output(
+        &ctx.usdc,
+        &Vec::from_array(&ctx.env, [xlm_output(&ctx), eurc_output(&ctx, amount(60))]),
+        &ctx.trader,
+        &0,
+        &10,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_multi_output_fail_duplicate() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(200));
+
+    ctx.broker_client.swap_multi_output(
+        &ctx.usdc,
+        &Vec::from_array(&ctx.env, [xlm_output(&ctx), xlm_output(&ctx)]),
+        &ctx.trader,
+        &0,
+        &10,
+        &None,
+    );
+}
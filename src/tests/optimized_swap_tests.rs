@@ -0,0 +1,137 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::route::Route;
+use soroban_sdk::Vec;
+
+// USDC -> EURC routes with the skewed split: most of the amount goes through the XLM path
+fn skewed_routes(ctx: &StrictSendTestContext, min: i128) -> Vec<Route> {
+    Vec::from_array(
+        &ctx.env,
+        [
+            Route {
+                amount: amount(90000),
+                min: min / 2,
+                estimated: min / 2,
+                path: ctx.path([
+                    ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                    ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+                ]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            },
+            Route {
+                amount: amount(10000),
+                min: min / 2,
+                estimated: min / 2,
+                path: ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            },
+        ],
+    )
+}
+
+fn swap_optimized(ctx: &StrictSendTestContext, routes: &Vec<Route>) -> Vec<i128> {
+    ctx.broker_client.swap_optimized(
+        &ctx.usdc,
+        routes,
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+    )
+}
+
+#[test]
+fn swap_optimized_rebalances_split() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(200000));
+    //skewed split can't reach the min amount
+    let plain = ctx.broker_client.try_swap(
+        &ctx.usdc,
+        &skewed_routes(&ctx, amount(80000)),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    );
+    assert!(plain.is_err());
+    //re-balanced split moves most of the amount to the deeper direct pool
+    let result = swap_optimized(&ctx, &skewed_routes(&ctx, amount(80000)));
+    assert_eq!(result.get(0).unwrap(), amount(100000));
+    assert!(result.get(1).unwrap() > amount(80000));
+    ctx.check_trader_balance(&ctx.usdc, amount(100000));
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    assert!(ctx.usdc_client.balance(&ctx.usdc_eurc_pool) > amount(12000000 + 90000));
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_optimized_single_route() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(100),
+            min: amount(70),
+            estimated: amount(70),
+            path: ctx.path([
+                ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+            ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
+        }],
+    );
+    //nothing to re-balance, the result matches the regular swap
+    swap_optimized(&ctx, &routes);
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_optimized_keeps_routes_with_alternatives() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100000));
+    let mut routes = skewed_routes(&ctx, amount(10000));
+    //route with fallback paths is not eligible for re-balancing
+    let mut direct = routes.get(1).unwrap();
+    direct.alternatives = Vec::from_array(&ctx.env, [routes.get(0).unwrap().path]);
+    routes.set(1, direct);
+    swap_optimized(&ctx, &routes);
+    assert_eq!(
+        ctx.usdc_client.balance(&ctx.usdc_eurc_pool),
+        amount(12000000 + 10000)
+    );
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_optimized_keeps_routes_sharing_pool() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100000));
+    let mut routes = skewed_routes(&ctx, amount(10000));
+    //the third route goes through the same pools as the first one
+    let mut shared = routes.get(0).unwrap();
+    shared.amount = amount(5000);
+    routes.push_back(shared);
+    let mut direct = routes.get(1).unwrap();
+    direct.amount = amount(5000);
+    routes.set(1, direct);
+    swap_optimized(&ctx, &routes);
+    //split is not re-balanced
+    assert_eq!(
+        ctx.usdc_client.balance(&ctx.usdc_eurc_pool),
+        amount(12000000 + 5000)
+    );
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_no_interim_leftovers();
+}
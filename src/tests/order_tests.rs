@@ -0,0 +1,204 @@
+extern crate std;
+use crate::tests::mock_malicious_lp_contract::{MaliciousLPContract, MaliciousLPContractClient};
+use crate::tests::swap_test_context::{amount, fake_asset, setup, StrictSendTestContext};
+use crate::types::{order::LimitOrder, protocol::Protocol, route::Route, step::PathStep};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Vec,
+};
+
+fn usdc_eurc_order(ctx: &StrictSendTestContext, price: i128) -> LimitOrder {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    LimitOrder {
+        trader: ctx.trader.clone(),
+        selling: ctx.usdc.clone(),
+        buying: ctx.eurc.clone(),
+        amount: amount(100),
+        price,
+        min: amount(70),
+        vfee: 0,
+        ffee: 10,
+        expiration: 1000,
+    }
+}
+
+#[test]
+fn execute_limit_order() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    ctx.broker_client.set_keeper_reward(&10);
+
+    let order_id = ctx
+        .broker_client
+        .place_order(&usdc_eurc_order(&ctx, 7_500_000));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    assert_eq!(ctx.usdc_client.balance(&ctx.broker), amount(100));
+    assert_eq!(ctx.broker_client.get_order(&order_id).amount, amount(100));
+
+    let result = ctx.broker_client.execute_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+
+    let reward = result.get(3).unwrap();
+    assert_eq!(reward, 819907);
+    assert_eq!(ctx.eurc_client.balance(&keeper), reward);
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    ctx.check_contract_fee_balance(result.get(2).unwrap());
+    ctx.check_no_interim_leftovers();
+    assert!(ctx.broker_client.try_get_order(&order_id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn execute_limit_order_fail_price() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_order(&usdc_eurc_order(&ctx, 9_000_000));
+
+    ctx.broker_client.execute_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32741")]
+fn execute_limit_order_fail_expired() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_order(&usdc_eurc_order(&ctx, 7_500_000));
+    ctx.env.ledger().set_sequence_number(1001);
+
+    ctx.broker_client.execute_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32732")]
+fn place_limit_order_fail_signed_only() {
+    let ctx = setup();
+    ctx.broker_client.require_signed_quotes(&true);
+    ctx.broker_client
+        .place_order(&usdc_eurc_order(&ctx, 7_500_000));
+}
+
+#[test]
+#[should_panic(expected = "32732")]
+fn execute_limit_order_fail_signed_only() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_order(&usdc_eurc_order(&ctx, 7_500_000));
+    //order placed before signed quotes were required
+    ctx.broker_client.require_signed_quotes(&true);
+
+    ctx.broker_client.execute_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+fn cancel_limit_order() {
+    let ctx = setup();
+    let keeper = Address::generate(&ctx.env);
+    let order_id = ctx
+        .broker_client
+        .place_order(&usdc_eurc_order(&ctx, 7_500_000));
+
+    //escrowed tokens can't be withdrawn by the admin
+    let dest = Address::generate(&ctx.env);
+    assert!(ctx
+        .broker_client
+        .try_withdraw(&dest, &ctx.usdc, &1)
+        .is_err());
+
+    ctx.broker_client.cancel_order(&order_id);
+    ctx.check_trader_balance(&ctx.usdc, amount(100));
+    ctx.check_contract_fee_balance(0);
+
+    //cancelled order can't be executed
+    assert!(ctx
+        .broker_client
+        .try_execute_order(
+            &order_id,
+            &ctx.usdc_eurc_routes(amount(100), amount(70)),
+            &ctx.fee_path(),
+            &keeper
+        )
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "32713")]
+fn attempt_escrowed_interim_asset_drain() {
+    let ctx = setup();
+    //XLM order escrowed on the contract balance
+    ctx.fund_trader(&ctx.xlm, amount(1000));
+    ctx.broker_client.place_order(&LimitOrder {
+        trader: ctx.trader.clone(),
+        selling: ctx.xlm.clone(),
+        buying: ctx.usdc.clone(),
+        amount: amount(1000),
+        price: 0,
+        min: amount(90),
+        vfee: 0,
+        ffee: 0,
+        expiration: 1000,
+    });
+
+    //malicious LP reports escrowed amount of XLM as the intermediate swap result
+    ctx.broker_client.enable_protocol(&Protocol::Comet, &true);
+    let btc = fake_asset(&ctx.env, &Address::generate(&ctx.env));
+    let lp_address = ctx.env.register(MaliciousLPContract, ());
+    MaliciousLPContractClient::new(&ctx.env, &lp_address).init(&btc, &amount(1000));
+    StellarAssetClient::new(&ctx.env, &btc).mint(&lp_address, &1);
+
+    let attacker = Address::generate(&ctx.env);
+    ctx.eurc_asset_client.mint(&attacker, &1);
+    let step = |asset: &Address| PathStep {
+        protocol: Protocol::Comet,
+        asset: asset.clone(),
+        pool: lp_address.clone(),
+        si: 0,
+        bi: 0,
+    };
+    ctx.broker_client.swap(
+        &ctx.eurc,
+        &Vec::from_array(
+            &ctx.env,
+            [Route {
+                amount: 1,
+                min: 0,
+                estimated: 0,
+                path: ctx.path([step(&ctx.xlm), step(&btc)]),
+                alternatives: Vec::new(&ctx.env),
+                graph: Vec::new(&ctx.env),
+            }],
+        ),
+        &attacker,
+        &0,
+        &0,
+        &ctx.path([]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    );
+}
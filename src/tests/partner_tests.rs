@@ -0,0 +1,96 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup};
+use soroban_sdk::{testutils::Address as _, Address, Vec};
+
+#[test]
+fn swap_with_partner_fee_share() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let partner = Address::generate(&ctx.env);
+    ctx.broker_client.set_partner(&partner, &2500);
+
+    let result = ctx.broker_client.swap(
+        &ctx.usdc,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &Some(partner.clone()),
+        &None,
+        &Vec::new(&ctx.env),
+    );
+
+    //25% of the received fee is credited to the partner
+    assert_eq!(result.get(2).unwrap(), 32826388);
+    assert_eq!(ctx.broker_client.partner_fees(&partner), 8206597);
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+
+    //partner fees can't be withdrawn by the admin
+    let dest = Address::generate(&ctx.env);
+    assert!(ctx
+        .broker_client
+        .try_withdraw(&dest, &ctx.usdc, &32826388)
+        .is_err());
+    ctx.broker_client
+        .withdraw(&dest, &ctx.usdc, &(32826388 - 8206597));
+
+    //claim accrued fees
+    assert_eq!(ctx.broker_client.claim_partner_fees(&partner), 8206597);
+    assert_eq!(ctx.usdc_client.balance(&partner), 8206597);
+    assert_eq!(ctx.broker_client.partner_fees(&partner), 0);
+    ctx.check_contract_fee_balance(0);
+}
+
+#[test]
+fn removed_partner_keeps_accrued_fees() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let partner = Address::generate(&ctx.env);
+    ctx.broker_client.set_partner(&partner, &10_000);
+
+    ctx.broker_client.swap(
+        &ctx.usdc,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &Some(partner.clone()),
+        &None,
+        &Vec::new(&ctx.env),
+    );
+    ctx.broker_client.remove_partner(&partner);
+
+    assert_eq!(ctx.broker_client.claim_partner_fees(&partner), 32826388);
+    ctx.check_contract_fee_balance(0);
+}
+
+#[test]
+#[should_panic(expected = "32720")]
+fn swap_fail_unknown_partner() {
+    let ctx = setup();
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let partner = Address::generate(&ctx.env);
+
+    ctx.broker_client.swap(
+        &ctx.usdc,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.path([]),
+        &Some(partner),
+        &None,
+        &Vec::new(&ctx.env),
+    );
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn set_partner_fail_share_overflow() {
+    let ctx = setup();
+    let partner = Address::generate(&ctx.env);
+    ctx.broker_client.set_partner(&partner, &10_001);
+}
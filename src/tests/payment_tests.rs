@@ -0,0 +1,96 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::route::Route;
+use soroban_sdk::{
+    symbol_short, testutils::Address as _, testutils::Events, Address, Bytes, IntoVal, Vec,
+};
+
+fn swap_and_pay(ctx: &StrictSendTestContext, merchant: &Address, payment: i128) -> Vec<i128> {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(100),
+            min: amount(70),
+            estimated: amount(70),
+            path: ctx.path([
+                ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+            ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
+        }],
+    );
+    ctx.broker_client.swap_and_pay(
+        &ctx.usdc,
+        &routes,
+        &ctx.trader,
+        merchant,
+        &payment,
+        &Bytes::from_slice(&ctx.env, b"INV-42"),
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+    )
+}
+
+#[test]
+fn swap_and_pay_invoice() {
+    let ctx = setup();
+    let merchant = Address::generate(&ctx.env);
+    let result = swap_and_pay(&ctx, &merchant, amount(75));
+    let events = ctx.env.events().all();
+    //merchant receives the exact amount, surplus is refunded to the payer,
+    //fee is charged only from the paid amount (32826388 if charged from the whole bought amount)
+    assert_eq!(
+        result,
+        Vec::from_array(
+            &ctx.env,
+            [amount(100), amount(75), 29727262, 803276356 - amount(75)]
+        )
+    );
+    assert_eq!(ctx.eurc_client.balance(&merchant), amount(75));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, 803276356 - amount(75));
+    ctx.check_contract_fee_balance(29727262);
+    ctx.check_no_interim_leftovers();
+    //payment event references the invoice
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        Vec::from_array(
+            &ctx.env,
+            [(
+                ctx.broker.clone(),
+                (
+                    symbol_short!("payment"),
+                    merchant.clone(),
+                    ctx.trader.clone()
+                )
+                    .into_val(&ctx.env),
+                (
+                    ctx.eurc.clone(),
+                    amount(75),
+                    Bytes::from_slice(&ctx.env, b"INV-42")
+                )
+                    .into_val(&ctx.env),
+            )]
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_and_pay_insufficient_output() {
+    let ctx = setup();
+    let merchant = Address::generate(&ctx.env);
+    swap_and_pay(&ctx, &merchant, amount(85));
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_and_pay_zero_amount() {
+    let ctx = setup();
+    let merchant = Address::generate(&ctx.env);
+    swap_and_pay(&ctx, &merchant, 0);
+}
@@ -0,0 +1,105 @@
+extern crate std;
+use crate::tests::mock_swap_hook_contract::MockSwapHookContract;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{payoutshare::PayoutShare, route::Route, swaphook::SwapHook};
+use soroban_sdk::{testutils::Address as _, Address, Bytes, Vec};
+
+fn share(recipient: &Address, share: u32) -> PayoutShare {
+    PayoutShare {
+        recipient: recipient.clone(),
+        share,
+    }
+}
+
+fn swap_split(
+    ctx: &StrictSendTestContext,
+    recipients: Vec<PayoutShare>,
+    hook: Option<SwapHook>,
+) -> Vec<i128> {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(100),
+            min: amount(70),
+            estimated: amount(70),
+            path: ctx.path([
+                ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+            ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
+        }],
+    );
+    ctx.broker_client.swap(
+        &ctx.usdc,
+        &routes,
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+        &hook,
+        &recipients,
+    )
+}
+
+#[test]
+fn swap_split_payout() {
+    let ctx = setup();
+    let first = Address::generate(&ctx.env);
+    let second = Address::generate(&ctx.env);
+    let third = Address::generate(&ctx.env);
+    let result = swap_split(
+        &ctx,
+        Vec::from_array(
+            &ctx.env,
+            [
+                share(&first, 3333),
+                share(&second, 3333),
+                share(&third, 3334),
+            ],
+        ),
+        None,
+    );
+    let bought = result.get(1).unwrap();
+    assert_eq!(bought, 800679106);
+    //the last recipient receives the rounding remainder
+    let part = bought * 3333 / 10000;
+    assert_eq!(ctx.eurc_client.balance(&first), part);
+    assert_eq!(ctx.eurc_client.balance(&second), part);
+    assert_eq!(ctx.eurc_client.balance(&third), bought - part * 2);
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, 0);
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_split_payout_invalid_shares() {
+    let ctx = setup();
+    let first = Address::generate(&ctx.env);
+    let second = Address::generate(&ctx.env);
+    swap_split(
+        &ctx,
+        Vec::from_array(&ctx.env, [share(&first, 5000), share(&second, 4000)]),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_split_payout_with_hook() {
+    let ctx = setup();
+    let recipient = Address::generate(&ctx.env);
+    let hook = SwapHook {
+        contract: ctx.env.register(MockSwapHookContract, ()),
+        payload: Bytes::from_slice(&ctx.env, &[1]),
+    };
+    swap_split(
+        &ctx,
+        Vec::from_array(&ctx.env, [share(&recipient, 10000)]),
+        Some(hook),
+    );
+}
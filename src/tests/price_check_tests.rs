@@ -0,0 +1,101 @@
+extern crate std;
+use crate::tests::mock_oracle_contract::{MockOracleContractClient, ORACLE_UNIT};
+use crate::tests::swap_test_context::{setup, StrictSendTestContext};
+use crate::types::oracle::Asset;
+use soroban_sdk::Symbol;
+
+// Oracle with USDC and EURC mapped to USD and EUR, 1% price tolerance
+fn setup_fiat_oracle<'a>(ctx: &'a StrictSendTestContext) -> MockOracleContractClient<'a> {
+    let oracle = ctx.setup_oracle();
+    ctx.broker_client.set_price_tolerance(&100);
+    let usd = Asset::Other(Symbol::new(&ctx.env, "USD"));
+    let eur = Asset::Other(Symbol::new(&ctx.env, "EUR"));
+    ctx.broker_client
+        .set_oracle_asset(&ctx.usdc, &Some(usd.clone()));
+    ctx.broker_client.set_oracle_asset(&ctx.eurc, &Some(eur));
+    oracle.set_price(&usd, &ORACLE_UNIT, &0);
+    oracle
+}
+
+fn set_eur_price(ctx: &StrictSendTestContext, oracle: &MockOracleContractClient, price: i128) {
+    oracle.set_price(
+        &Asset::Other(Symbol::new(&ctx.env, "EUR")),
+        &(ORACLE_UNIT / 100 * price),
+        &0,
+    );
+}
+
+#[test]
+fn swap_within_oracle_tolerance() {
+    let ctx = setup();
+    let oracle = setup_fiat_oracle(&ctx);
+    set_eur_price(&ctx, &oracle, 120);
+
+    let result = ctx.swap_usdc_eurc();
+    assert_eq!(result.get(2).unwrap(), 32826388);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32751")]
+fn swap_fail_oracle_deviation() {
+    let ctx = setup();
+    let oracle = setup_fiat_oracle(&ctx);
+    set_eur_price(&ctx, &oracle, 110);
+
+    ctx.swap_usdc_eurc();
+}
+
+#[test]
+#[should_panic(expected = "32751")]
+fn swap_fail_oracle_deviation_unmapped_token() {
+    let ctx = setup();
+    let oracle = setup_fiat_oracle(&ctx);
+    //unmapped token is quoted by the oracle as a Stellar asset
+    ctx.broker_client.set_oracle_asset(&ctx.eurc, &None);
+    oracle.set_price(
+        &Asset::Stellar(ctx.eurc.clone()),
+        &(ORACLE_UNIT / 100 * 110),
+        &0,
+    );
+
+    ctx.swap_usdc_eurc();
+}
+
+#[test]
+fn swap_oracle_deviation_tolerance() {
+    let ctx = setup();
+    let oracle = setup_fiat_oracle(&ctx);
+    set_eur_price(&ctx, &oracle, 110);
+    ctx.broker_client.set_price_tolerance(&1000);
+
+    ctx.swap_usdc_eurc();
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+}
+
+#[test]
+fn swap_oracle_check_disabled() {
+    let ctx = setup();
+    let oracle = setup_fiat_oracle(&ctx);
+    set_eur_price(&ctx, &oracle, 110);
+
+    //disabled check
+    ctx.broker_client.set_price_tolerance(&0);
+    ctx.swap_usdc_eurc();
+
+    //unmapped token not quoted by the oracle
+    ctx.broker_client.set_price_tolerance(&100);
+    ctx.broker_client.set_oracle_asset(&ctx.eurc, &None);
+    let result = ctx.swap_usdc_eurc();
+    assert!(result.get(1).unwrap() > 0);
+}
+
+#[test]
+#[should_panic(expected = "32750")]
+fn swap_fail_oracle_price_unavailable() {
+    let ctx = setup();
+    setup_fiat_oracle(&ctx);
+
+    ctx.swap_usdc_eurc();
+}
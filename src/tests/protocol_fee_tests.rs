@@ -0,0 +1,95 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{protocol::Protocol, route::Route};
+use soroban_sdk::Vec;
+
+// XLM -> USDC through Aqua
+fn aqua_route(ctx: &StrictSendTestContext) -> Route {
+    Route {
+        amount: amount(400),
+        min: amount(30),
+        estimated: amount(30),
+        path: ctx.path([ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone())]),
+        alternatives: Vec::new(&ctx.env),
+        graph: Vec::new(&ctx.env),
+    }
+}
+
+// XLM -> EURC -> USDC through Soroswap and Aqua
+fn mixed_route(ctx: &StrictSendTestContext) -> Route {
+    Route {
+        amount: amount(600),
+        min: amount(50),
+        estimated: amount(50),
+        path: ctx.path([
+            ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+            ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone()),
+        ]),
+        alternatives: Vec::new(&ctx.env),
+        graph: Vec::new(&ctx.env),
+    }
+}
+
+fn swap_xlm_usdc(ctx: &StrictSendTestContext, routes: Vec<Route>, ffee: u32) -> Vec<i128> {
+    ctx.fund_trader(&ctx.xlm, amount(1000));
+    ctx.broker_client.swap(
+        &ctx.xlm,
+        &routes,
+        &ctx.trader,
+        &0,
+        &ffee,
+        &ctx.path([]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    )
+}
+
+#[test]
+fn swap_with_protocol_fees() {
+    //bought amounts for each route without fees
+    let ctx = setup();
+    let aqua_bought = swap_xlm_usdc(&ctx, Vec::from_array(&ctx.env, [aqua_route(&ctx)]), 0)
+        .get(1)
+        .unwrap();
+    let ctx = setup();
+    let mixed_bought = swap_xlm_usdc(&ctx, Vec::from_array(&ctx.env, [mixed_route(&ctx)]), 0)
+        .get(1)
+        .unwrap();
+
+    let ctx = setup();
+    ctx.broker_client
+        .set_protocol_fee(&Protocol::AquaConstant, &5);
+    ctx.broker_client.set_protocol_fee(&Protocol::Soroswap, &10);
+    ctx.broker_client.enable_protocol_fees(&true);
+
+    //caller-supplied fixed fee is ignored
+    let routes = Vec::from_array(&ctx.env, [aqua_route(&ctx), mixed_route(&ctx)]);
+    let result = swap_xlm_usdc(&ctx, routes, 100);
+
+    //5‰ charged from the first route and 15‰ from the second route
+    let fee = aqua_bought * 5 / 1000 + mixed_bought * 15 / 1000;
+    assert_eq!(result.get(1).unwrap(), aqua_bought + mixed_bought - fee);
+    assert_eq!(result.get(2).unwrap(), fee);
+    ctx.check_contract_fee_balance(fee);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_with_protocol_fees_disabled() {
+    let ctx = setup();
+    ctx.broker_client
+        .set_protocol_fee(&Protocol::AquaConstant, &5);
+    ctx.broker_client.enable_protocol_fees(&true);
+    ctx.broker_client.enable_protocol_fees(&false);
+
+    let result = swap_xlm_usdc(&ctx, Vec::from_array(&ctx.env, [aqua_route(&ctx)]), 0);
+    assert_eq!(result.get(2).unwrap(), 0);
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn set_protocol_fee_fail_overflow() {
+    let ctx = setup();
+    ctx.broker_client.set_protocol_fee(&Protocol::Comet, &1001);
+}
@@ -0,0 +1,179 @@
+extern crate std;
+use crate::tests::mock_aqua_pool_contract::{MockAquaPoolContract, MockAquaPoolContractClient};
+use crate::tests::swap_test_context::{amount, fake_asset, setup, StrictSendTestContext};
+use crate::types::{
+    protocol::Protocol, rebalancetarget::RebalanceTarget, route::Route, step::PathStep,
+    swapinput::SwapInput,
+};
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Vec,
+};
+
+// Register additional GBP token with EURC/GBP pool, returns token and GBP->EURC path step
+fn setup_gbp(ctx: &StrictSendTestContext) -> (Address, PathStep) {
+    let gbp = fake_asset(&ctx.env, &Address::generate(&ctx.env));
+    let pool = ctx.env.register(MockAquaPoolContract, ());
+    MockAquaPoolContractClient::new(&ctx.env, &pool).init(
+        &Vec::from_array(&ctx.env, [ctx.eurc.clone(), gbp.clone()]),
+        &Vec::from_array(&ctx.env, [amount(1150000) as u128, amount(1000000) as u128]),
+    );
+    ctx.eurc_asset_client.mint(&pool, &amount(1150000));
+    StellarAssetClient::new(&ctx.env, &gbp).mint(&pool, &amount(1000000));
+    StellarAssetClient::new(&ctx.env, &gbp).mint(&ctx.trader, &amount(50));
+    let step = PathStep {
+        protocol: Protocol::AquaConstant,
+        asset: ctx.eurc.clone(),
+        pool,
+        si: 1,
+        bi: 0,
+    };
+    (gbp, step)
+}
+
+fn route(amount: i128, path: Vec<PathStep>) -> Route {
+    Route {
+        amount,
+        min: 1,
+        estimated: 1,
+        alternatives: Vec::new(path.env()),
+        graph: Vec::new(path.env()),
+        path,
+    }
+}
+
+fn targets(ctx: &StrictSendTestContext, eurc_min: i128) -> Vec<RebalanceTarget> {
+    Vec::from_array(
+        &ctx.env,
+        [
+            RebalanceTarget {
+                buying: ctx.xlm.clone(),
+                min: 1,
+                fpath: ctx.path([ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone())]),
+            },
+            RebalanceTarget {
+                buying: ctx.eurc.clone(),
+                min: eurc_min,
+                fpath: ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone())]),
+            },
+        ],
+    )
+}
+
+fn inputs(ctx: &StrictSendTestContext, gbp: &Address, gbp_step: &PathStep) -> Vec<SwapInput> {
+    Vec::from_array(
+        &ctx.env,
+        [
+            SwapInput {
+                selling: ctx.usdc.clone(),
+                routes: Vec::from_array(
+                    &ctx.env,
+                    [
+                        route(
+                            amount(60),
+                            ctx.path([ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())]),
+                        ),
+                        route(
+                            amount(40),
+                            ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())]),
+                        ),
+                    ],
+                ),
+            },
+            SwapInput {
+                selling: gbp.clone(),
+                routes: Vec::from_array(
+                    &ctx.env,
+                    [route(amount(50), ctx.path([gbp_step.clone()]))],
+                ),
+            },
+        ],
+    )
+}
+
+#[test]
+fn rebalance() {
+    let ctx = setup();
+    let (gbp, gbp_step) = setup_gbp(&ctx);
+    ctx.fund_trader(&ctx.usdc, amount(100));
+
+    let result = ctx.broker_client.rebalance(
+        &inputs(&ctx, &gbp, &gbp_step),
+        &targets(&ctx, amount(80)),
+        &ctx.trader,
+        &0,
+        &10,
+        &None,
+    );
+
+    assert_eq!(result.len(), 4);
+    assert_eq!(result.get(ctx.usdc.clone()).unwrap(), -amount(100));
+    assert_eq!(result.get(gbp.clone()).unwrap(), -amount(50));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    assert_eq!(TokenClient::new(&ctx.env, &gbp).balance(&ctx.trader), 0);
+    ctx.check_trader_balance(&ctx.xlm, result.get(ctx.xlm.clone()).unwrap());
+    ctx.check_trader_balance(&ctx.eurc, result.get(ctx.eurc.clone()).unwrap());
+    assert!(ctx.usdc_client.balance(&ctx.broker) > 0);
+    ctx.check_no_interim_leftovers();
+    assert_eq!(TokenClient::new(&ctx.env, &gbp).balance(&ctx.broker), 0);
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn rebalance_fail_target_min() {
+    let ctx = setup();
+    let (gbp, gbp_step) = setup_gbp(&ctx);
+    ctx.fund_trader(&ctx.usdc, amount(100));
+
+    ctx.broker_client.rebalance(
+        &inputs(&ctx, &gbp, &gbp_step),
+        &targets(&ctx, amount(100)),
+        &ctx.trader,
+        &0,
+        &10,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn rebalance_fail_unknown_target() {
+    let ctx = setup();
+    let (gbp, gbp_step) = setup_gbp(&ctx);
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let mut targets = targets(&ctx, 0);
+    targets.pop_back();
+
+    ctx.broker_client.rebalance(
+        &inputs(&ctx, &gbp, &gbp_step),
+        &targets,
+        &ctx.trader,
+        &0,
+        &10,
+        &None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn rebalance_fail_overlap() {
+    let ctx = setup();
+    let (gbp, gbp_step) = setup_gbp(&ctx);
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let mut targets = targets(&ctx, 0);
+    targets.push_back(RebalanceTarget {
+        buying: gbp.clone(),
+        min: 0,
+        fpath: Vec::new(&ctx.env),
+    });
+
+    ctx.broker_client.rebalance(
+        &inputs(&ctx, &gbp, &gbp_step),
+        &targets,
+        &ctx.trader,
+        &0,
+        &10,
+        &None,
+    );
+}
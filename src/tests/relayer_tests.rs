@@ -0,0 +1,97 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup};
+use crate::types::relayerfee::RelayerFee;
+use soroban_sdk::{
+    testutils::{Address as _, AuthorizedFunction},
+    Address, IntoVal, Symbol,
+};
+
+#[test]
+fn swap_relayed_fee_in_buying_token() {
+    let ctx = setup();
+    let relayer = Address::generate(&ctx.env);
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let relayer_fee = RelayerFee {
+        amount: amount(1),
+        token: ctx.eurc.clone(),
+    };
+
+    let result = ctx
+        .broker_client
+        .swap_relayed(&request, &relayer_fee, &relayer);
+
+    //trader authorized the request and relayer fee without binding the relayer
+    let (_, invocation) = ctx
+        .env
+        .auths()
+        .into_iter()
+        .find(|(address, _)| address == &ctx.trader)
+        .unwrap();
+    match invocation.function {
+        AuthorizedFunction::Contract((contract, function, args)) => {
+            assert_eq!(contract, ctx.broker);
+            assert_eq!(function, Symbol::new(&ctx.env, "swap_relayed"));
+            assert_eq!(args, (request, relayer_fee).into_val(&ctx.env));
+        }
+        _ => panic!("Unexpected invocation"),
+    }
+
+    assert_eq!(result.get(3).unwrap(), amount(1));
+    assert_eq!(ctx.eurc_client.balance(&relayer), amount(1));
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_trader_balance(&ctx.eurc, 800679106 - amount(1));
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_relayed_fee_in_fee_token() {
+    let ctx = setup();
+    let relayer = Address::generate(&ctx.env);
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let relayer_fee = RelayerFee {
+        amount: amount(1),
+        token: ctx.usdc.clone(),
+    };
+
+    let result = ctx
+        .broker_client
+        .swap_relayed(&request, &relayer_fee, &relayer);
+
+    //relayer fee is converted to fee tokens along with the broker fee
+    let relayer_received = result.get(3).unwrap();
+    assert_eq!(relayer_received, 11932370);
+    assert_eq!(ctx.usdc_client.balance(&relayer), relayer_received);
+    ctx.check_contract_fee_balance(result.get(2).unwrap());
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32701")]
+fn swap_relayed_fail_invalid_fee_token() {
+    let ctx = setup();
+    let relayer = Address::generate(&ctx.env);
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let relayer_fee = RelayerFee {
+        amount: amount(1),
+        token: ctx.xlm.clone(),
+    };
+
+    ctx.broker_client
+        .swap_relayed(&request, &relayer_fee, &relayer);
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_relayed_fail_fee_exceeds_output() {
+    let ctx = setup();
+    let relayer = Address::generate(&ctx.env);
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let relayer_fee = RelayerFee {
+        amount: amount(100),
+        token: ctx.eurc.clone(),
+    };
+
+    ctx.broker_client
+        .swap_relayed(&request, &relayer_fee, &relayer);
+}
@@ -0,0 +1,193 @@
+extern crate std;
+use crate::tests::mock_aqua_pool_contract::{MockAquaPoolContract, MockAquaPoolContractClient};
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{protocol::Protocol, route::Route, step::PathStep};
+use soroban_sdk::Vec;
+
+// USDC -> EURC path step through the pool with worse price
+fn expensive_step(ctx: &StrictSendTestContext) -> PathStep {
+    let pool = ctx.env.register(MockAquaPoolContract, ());
+    MockAquaPoolContractClient::new(&ctx.env, &pool).init(
+        &Vec::from_array(&ctx.env, [ctx.usdc.clone(), ctx.eurc.clone()]),
+        &Vec::from_array(
+            &ctx.env,
+            [amount(15000000) as u128, amount(10000000) as u128],
+        ),
+    );
+    ctx.usdc_asset_client.mint(&pool, &amount(15000000));
+    ctx.eurc_asset_client.mint(&pool, &amount(10000000));
+    PathStep {
+        protocol: Protocol::AquaConstant,
+        asset: ctx.eurc.clone(),
+        pool,
+        si: 0,
+        bi: 1,
+    }
+}
+
+// USDC -> XLM -> EURC path
+fn xlm_path(ctx: &StrictSendTestContext) -> Vec<PathStep> {
+    ctx.path([
+        ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+        ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+    ])
+}
+
+fn swap_usdc_eurc(
+    ctx: &StrictSendTestContext,
+    min: i128,
+    path: Vec<PathStep>,
+    alternatives: Vec<Vec<PathStep>>,
+) -> Vec<i128> {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let routes = Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(100),
+            min,
+            estimated: min,
+            path,
+            alternatives,
+            graph: Vec::new(&ctx.env),
+        }],
+    );
+    ctx.broker_client.swap(
+        &ctx.usdc,
+        &routes,
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    )
+}
+
+#[test]
+fn swap_fallback_on_failed_path() {
+    let ctx = setup();
+    let result = swap_usdc_eurc(
+        &ctx,
+        amount(70),
+        ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+        Vec::from_array(&ctx.env, [xlm_path(&ctx)]),
+    );
+
+    //same result as the direct swap through the alternative path
+    assert_eq!(result.get(2).unwrap(), 32826388);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+fn swap_fallback_on_min() {
+    let ctx = setup();
+    //direct path through the pool with worse price yields less than min
+    let result = swap_usdc_eurc(
+        &ctx,
+        amount(80),
+        ctx.path([expensive_step(&ctx)]),
+        Vec::from_array(&ctx.env, [xlm_path(&ctx)]),
+    );
+
+    //same result as the direct swap through the alternative path
+    let direct_ctx = setup();
+    let direct_result = swap_usdc_eurc(
+        &direct_ctx,
+        amount(80),
+        xlm_path(&direct_ctx),
+        Vec::new(&direct_ctx.env),
+    );
+    assert_eq!(result, direct_result);
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_fallback_fail_all_paths() {
+    let ctx = setup();
+    swap_usdc_eurc(
+        &ctx,
+        amount(90),
+        ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())]),
+        Vec::from_array(
+            &ctx.env,
+            [
+                ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+                xlm_path(&ctx),
+            ],
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "32711")]
+fn swap_fallback_fail_multi_step_primary() {
+    let ctx = setup();
+    //path failed after the first step can't be reverted, so fallbacks would never run
+    swap_usdc_eurc(
+        &ctx,
+        amount(70),
+        ctx.path([
+            ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+            ctx.broken_step(ctx.eurc.clone()),
+        ]),
+        Vec::from_array(&ctx.env, [xlm_path(&ctx)]),
+    );
+}
+
+#[test]
+fn swap_fallback_multi_step_last_path() {
+    let ctx = setup();
+    //the last path may have several steps since nothing follows it
+    let result = swap_usdc_eurc(
+        &ctx,
+        amount(70),
+        ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+        Vec::from_array(
+            &ctx.env,
+            [
+                ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+                xlm_path(&ctx),
+            ],
+        ),
+    );
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32711")]
+fn swap_fallback_fail_multi_step_alternative() {
+    let ctx = setup();
+    swap_usdc_eurc(
+        &ctx,
+        amount(70),
+        ctx.path([ctx.broken_step(ctx.eurc.clone())]),
+        Vec::from_array(
+            &ctx.env,
+            [
+                xlm_path(&ctx),
+                ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone())]),
+            ],
+        ),
+    );
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn swap_fallback_fail_different_buying_token() {
+    let ctx = setup();
+    swap_usdc_eurc(
+        &ctx,
+        amount(70),
+        xlm_path(&ctx),
+        Vec::from_array(
+            &ctx.env,
+            [ctx.path([ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone())])],
+        ),
+    );
+}
@@ -0,0 +1,108 @@
+extern crate std;
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::swaprequest::SwapRequest;
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{testutils::Ledger, xdr::ToXdr, Bytes, BytesN, Vec};
+
+const SIGNER_SECRET: [u8; 32] = [7; 32];
+
+fn signer() -> SigningKey {
+    SigningKey::from_bytes(&SIGNER_SECRET)
+}
+
+fn init_signer(ctx: &StrictSendTestContext) {
+    let public_key = BytesN::from_array(&ctx.env, &signer().verifying_key().to_bytes());
+    ctx.broker_client.set_quote_signer(&Some(public_key));
+}
+
+fn sign(
+    ctx: &StrictSendTestContext,
+    request: &SwapRequest,
+    nonce: u64,
+    expiration: u32,
+) -> BytesN<64> {
+    let payload = (ctx.broker.clone(), request.clone(), nonce, expiration).to_xdr(&ctx.env);
+    let hash = Bytes::from(ctx.env.crypto().sha256(&payload));
+    let mut message = [0u8; 32];
+    hash.copy_into_slice(&mut message);
+    BytesN::from_array(&ctx.env, &signer().sign(&message).to_bytes())
+}
+
+#[test]
+fn swap_signed_quote() {
+    let ctx = setup();
+    init_signer(&ctx);
+    ctx.broker_client.require_signed_quotes(&true);
+
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let signature = sign(&ctx, &request, 1, 100);
+    ctx.broker_client
+        .swap_signed(&request, &1, &100, &signature);
+
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_trader_balance(&ctx.eurc, 800679106);
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_no_interim_leftovers();
+}
+
+#[test]
+#[should_panic(expected = "32731")]
+fn swap_signed_fail_replay() {
+    let ctx = setup();
+    init_signer(&ctx);
+
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let signature = sign(&ctx, &request, 1, 100);
+    ctx.broker_client
+        .swap_signed(&request, &1, &100, &signature);
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    ctx.broker_client
+        .swap_signed(&request, &1, &100, &signature);
+}
+
+#[test]
+#[should_panic(expected = "32730")]
+fn swap_signed_fail_expired() {
+    let ctx = setup();
+    init_signer(&ctx);
+
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let signature = sign(&ctx, &request, 1, 100);
+    ctx.env.ledger().set_sequence_number(101);
+    ctx.broker_client
+        .swap_signed(&request, &1, &100, &signature);
+}
+
+#[test]
+#[should_panic(expected = "Error(Crypto, InvalidInput)")]
+fn swap_signed_fail_tampered_fee() {
+    let ctx = setup();
+    init_signer(&ctx);
+
+    let mut request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    let signature = sign(&ctx, &request, 1, 100);
+    request.vfee = 0;
+    ctx.broker_client
+        .swap_signed(&request, &1, &100, &signature);
+}
+
+#[test]
+#[should_panic(expected = "32732")]
+fn swap_fail_unsigned_quote() {
+    let ctx = setup();
+    init_signer(&ctx);
+    ctx.broker_client.require_signed_quotes(&true);
+
+    let request = ctx.usdc_eurc_request(&ctx.trader, amount(70));
+    ctx.broker_client.swap(
+        &request.selling,
+        &request.routes,
+        &request.trader,
+        &request.vfee,
+        &request.ffee,
+        &request.fpath,
+        &request.partner,
+        &None,
+        &Vec::new(&ctx.env),
+    );
+}
@@ -20,6 +20,8 @@ fn swap_usdc_eurc() {
                 ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
                 ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
             ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
         }],
     );
 
@@ -29,10 +31,10 @@ fn swap_usdc_eurc() {
         &ctx.trader,
         &150,
         &10,
-        &ctx.path([
-            ctx.step(&ctx.xlm_eurc_pool, ctx.xlm.clone()),
-            ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone()),
-        ]),
+        &ctx.fee_path(),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
     );
 
     ctx.check_contract_fee_balance(32826388);
@@ -53,15 +55,23 @@ fn swap_eurc_usdc() {
             amount: amount(55),
             min: amount(60),
             estimated: amount(60),
-            path: ctx.path([
-                ctx.step(&ctx.xlm_eurc_pool, ctx.xlm.clone()),
-                ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone()),
-            ]),
+            path: ctx.fee_path(),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
         }],
     );
 
-    ctx.broker_client
-        .swap(&ctx.eurc, &swaps, &ctx.trader, &300, &0, &ctx.path([]));
+    ctx.broker_client.swap(
+        &ctx.eurc,
+        &swaps,
+        &ctx.trader,
+        &300,
+        &0,
+        &ctx.path([]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    );
 
     ctx.check_contract_fee_balance(16790041);
     ctx.check_trader_balance(&ctx.eurc, amount(45));
@@ -85,6 +95,8 @@ fn swap_xlm_eurc() {
                 ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone()),
                 ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone()),
             ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
         }],
     );
 
@@ -95,6 +107,9 @@ fn swap_xlm_eurc() {
         &300,
         &0,
         &ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone())]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
     );
 
     ctx.check_contract_fee_balance(1665767);
@@ -120,6 +135,8 @@ fn swap_no_fees() {
                 ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone()),
                 ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone()),
             ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
         }],
     );
 
@@ -130,6 +147,9 @@ fn swap_no_fees() {
         &300,
         &0,
         &ctx.path([ctx.step(&ctx.usdc_eurc_pool, ctx.usdc.clone())]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
     );
 
     ctx.check_contract_fee_balance(0);
@@ -156,11 +176,22 @@ fn swap_fail_less_min() {
                 ctx.step(&ctx.usdc_xlm_pool, ctx.usdc.clone()),
                 ctx.step(&ctx.usdc_eurc_pool, ctx.eurc.clone()),
             ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
         }],
     );
 
-    ctx.broker_client
-        .swap(&ctx.xlm, &swaps, &ctx.trader, &300, &0, &ctx.path([]));
+    ctx.broker_client.swap(
+        &ctx.xlm,
+        &swaps,
+        &ctx.trader,
+        &300,
+        &0,
+        &ctx.path([]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    );
 }
 
 #[test]
@@ -183,11 +214,22 @@ fn swap_fail_invalid_step() {
                 si: 0,
                 bi: 2,
             }]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
         }],
     );
 
-    ctx.broker_client
-        .swap(&ctx.xlm, &swaps, &ctx.trader, &300, &0, &ctx.path([]));
+    ctx.broker_client.swap(
+        &ctx.xlm,
+        &swaps,
+        &ctx.trader,
+        &300,
+        &0,
+        &ctx.path([]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
+    );
 }
 
 #[test]
@@ -210,6 +252,8 @@ fn swap_fail_protocol_disabled() {
                 si: 0,
                 bi: 2,
             }]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
         }],
     );
 
@@ -220,5 +264,8 @@ fn swap_fail_protocol_disabled() {
         &300,
         &0,
         &ctx.path([]),
+        &None,
+        &None,
+        &Vec::new(&ctx.env),
     );
 }
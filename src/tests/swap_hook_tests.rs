@@ -0,0 +1,83 @@
+extern crate std;
+use crate::tests::mock_swap_hook_contract::{MockSwapHookContract, MockSwapHookContractClient};
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{route::Route, swaphook::SwapHook};
+use soroban_sdk::{Address, Bytes, Vec};
+
+fn routes(ctx: &StrictSendTestContext) -> Vec<Route> {
+    Vec::from_array(
+        &ctx.env,
+        [Route {
+            amount: amount(100),
+            min: amount(70),
+            estimated: amount(70),
+            path: ctx.path([
+                ctx.step(&ctx.usdc_xlm_pool, ctx.xlm.clone()),
+                ctx.step(&ctx.xlm_eurc_pool, ctx.eurc.clone()),
+            ]),
+            alternatives: Vec::new(&ctx.env),
+            graph: Vec::new(&ctx.env),
+        }],
+    )
+}
+
+fn hook(ctx: &StrictSendTestContext, contract: &Address, payload: &[u8]) -> SwapHook {
+    SwapHook {
+        contract: contract.clone(),
+        payload: Bytes::from_slice(&ctx.env, payload),
+    }
+}
+
+#[test]
+fn swap_with_hook() {
+    let ctx = setup();
+    let contract = ctx.env.register(MockSwapHookContract, ());
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let result = ctx.broker_client.swap(
+        &ctx.usdc,
+        &routes(&ctx),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+        &Some(hook(&ctx, &contract, &[7, 7])),
+        &Vec::new(&ctx.env),
+    );
+    assert_eq!(result.get(1).unwrap(), 800679106);
+    //bought tokens are transferred to the hook contract
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    ctx.check_trader_balance(&ctx.eurc, 0);
+    assert_eq!(ctx.eurc_client.balance(&contract), 800679106);
+    ctx.check_contract_fee_balance(32826388);
+    ctx.check_no_interim_leftovers();
+    let (trader, token, bought, payload) =
+        MockSwapHookContractClient::new(&ctx.env, &contract).last_swap();
+    assert_eq!(trader, ctx.trader);
+    assert_eq!(token, ctx.eurc);
+    assert_eq!(bought, 800679106);
+    assert_eq!(payload, Bytes::from_slice(&ctx.env, &[7, 7]));
+}
+
+#[test]
+fn swap_reverted_by_hook() {
+    let ctx = setup();
+    let contract = ctx.env.register(MockSwapHookContract, ());
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    let result = ctx.broker_client.try_swap(
+        &ctx.usdc,
+        &routes(&ctx),
+        &ctx.trader,
+        &150,
+        &10,
+        &ctx.fee_path(),
+        &None,
+        &Some(hook(&ctx, &contract, &[])),
+        &Vec::new(&ctx.env),
+    );
+    assert!(result.is_err());
+    //the whole swap is reverted
+    ctx.check_trader_balance(&ctx.usdc, amount(100));
+    assert_eq!(ctx.eurc_client.balance(&contract), 0);
+    ctx.check_contract_fee_balance(0);
+}
@@ -1,10 +1,11 @@
 extern crate std;
 use crate::{
     tests::mock_aqua_pool_contract::{MockAquaPoolContract, MockAquaPoolContractClient},
+    tests::mock_oracle_contract::{MockOracleContract, MockOracleContractClient},
     tests::mock_soroswap_pair_contract::{
         MockSoroswapPairContract, MockSoroswapPairContractClient,
     },
-    types::{protocol::Protocol, step::PathStep},
+    types::{protocol::Protocol, route::Route, step::PathStep, swaprequest::SwapRequest},
     StellarBroker, StellarBrokerClient,
 };
 use soroban_sdk::{
@@ -74,13 +75,7 @@ impl StrictSendTestContext<'_> {
             } else {
                 bi = 1;
             }
-        } else if pool == &self.usdc_eurc_pool {
-            if buying == self.usdc {
-                si = 1;
-            } else {
-                bi = 1;
-            }
-        } else if pool == &self.usdc_xlm_pool {
+        } else if pool == &self.usdc_eurc_pool || pool == &self.usdc_xlm_pool {
             if buying == self.usdc {
                 si = 1;
             } else {
@@ -102,6 +97,88 @@ impl StrictSendTestContext<'_> {
         Vec::from_array(&self.env, steps)
     }
 
+    // Path step through the pool which fails on swap
+    pub fn broken_step(&self, buying: Address) -> PathStep {
+        PathStep {
+            protocol: Protocol::AquaConstant,
+            asset: buying,
+            pool: self.env.register(MockAquaPoolContract, ()),
+            si: 0,
+            bi: 1,
+        }
+    }
+
+    pub fn fee_path(&self) -> Vec<PathStep> {
+        self.path([
+            self.step(&self.xlm_eurc_pool, self.xlm.clone()),
+            self.step(&self.usdc_xlm_pool, self.usdc.clone()),
+        ])
+    }
+
+    pub fn usdc_eurc_routes(&self, amount: i128, min: i128) -> Vec<Route> {
+        Vec::from_array(
+            &self.env,
+            [Route {
+                amount,
+                min,
+                estimated: min,
+                path: self.path([
+                    self.step(&self.usdc_xlm_pool, self.xlm.clone()),
+                    self.step(&self.xlm_eurc_pool, self.eurc.clone()),
+                ]),
+                alternatives: Vec::new(&self.env),
+                graph: Vec::new(&self.env),
+            }],
+        )
+    }
+
+    pub fn usdc_eurc_request(&self, trader: &Address, min: i128) -> SwapRequest {
+        self.usdc_asset_client.mint(trader, &amount(100));
+        SwapRequest {
+            selling: self.usdc.clone(),
+            routes: Vec::from_array(
+                &self.env,
+                [Route {
+                    amount: amount(100),
+                    min,
+                    estimated: amount(70),
+                    path: self.path([
+                        self.step(&self.usdc_xlm_pool, self.xlm.clone()),
+                        self.step(&self.xlm_eurc_pool, self.eurc.clone()),
+                    ]),
+                    alternatives: Vec::new(&self.env),
+                    graph: Vec::new(&self.env),
+                }],
+            ),
+            trader: trader.clone(),
+            vfee: 150,
+            ffee: 10,
+            fpath: self.fee_path(),
+            partner: None,
+        }
+    }
+
+    pub fn swap_usdc_eurc(&self) -> Vec<i128> {
+        self.fund_trader(&self.usdc, amount(100));
+        self.broker_client.swap(
+            &self.usdc,
+            &self.usdc_eurc_routes(amount(100), amount(70)),
+            &self.trader,
+            &150,
+            &10,
+            &self.fee_path(),
+            &None,
+            &None,
+            &Vec::new(&self.env),
+        )
+    }
+
+    pub fn setup_oracle(&self) -> MockOracleContractClient<'_> {
+        let oracle = self.env.register(MockOracleContract, ());
+        self.broker_client.set_oracle(&Some(oracle.clone()));
+        MockOracleContractClient::new(&self.env, &oracle)
+    }
+
     fn get_token_client(&self, asset: &Address) -> &TokenClient<'_> {
         if asset == &self.xlm {
             return &self.xlm_client;
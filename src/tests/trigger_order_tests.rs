@@ -0,0 +1,215 @@
+extern crate std;
+use crate::tests::mock_oracle_contract::{MockOracleContractClient, ORACLE_UNIT};
+use crate::tests::swap_test_context::{amount, setup, StrictSendTestContext};
+use crate::types::{
+    oracle::Asset,
+    triggerorder::{TriggerKind, TriggerOrder},
+};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+};
+
+// Oracle quoting EURC at one base asset unit
+fn setup_eurc_oracle<'a>(ctx: &'a StrictSendTestContext) -> MockOracleContractClient<'a> {
+    let oracle = ctx.setup_oracle();
+    oracle.set_price(&Asset::Stellar(ctx.eurc.clone()), &ORACLE_UNIT, &0);
+    oracle
+}
+
+fn set_usdc_price(ctx: &StrictSendTestContext, oracle: &MockOracleContractClient, price: i128) {
+    oracle.set_price(
+        &Asset::Stellar(ctx.usdc.clone()),
+        &(ORACLE_UNIT / 100 * price),
+        &ctx.env.ledger().timestamp(),
+    );
+}
+
+fn usdc_eurc_order(ctx: &StrictSendTestContext, kind: TriggerKind, trigger: i128) -> TriggerOrder {
+    ctx.fund_trader(&ctx.usdc, amount(100));
+    TriggerOrder {
+        trader: ctx.trader.clone(),
+        selling: ctx.usdc.clone(),
+        buying: ctx.eurc.clone(),
+        amount: amount(100),
+        kind,
+        trigger,
+        min: amount(70),
+        vfee: 0,
+        ffee: 10,
+        expiration: 1000,
+    }
+}
+
+#[test]
+fn execute_stop_loss_order() {
+    let ctx = setup();
+    let oracle = setup_eurc_oracle(&ctx);
+    let keeper = Address::generate(&ctx.env);
+    set_usdc_price(&ctx, &oracle, 95);
+
+    let order_id = ctx.broker_client.place_trigger_order(&usdc_eurc_order(
+        &ctx,
+        TriggerKind::StopLoss,
+        9_000_000,
+    ));
+    ctx.check_trader_balance(&ctx.usdc, 0);
+    assert_eq!(ctx.usdc_client.balance(&ctx.broker), amount(100));
+
+    //price above the trigger
+    assert!(ctx
+        .broker_client
+        .try_execute_trigger_order(
+            &order_id,
+            &ctx.usdc_eurc_routes(amount(100), amount(70)),
+            &ctx.fee_path(),
+            &keeper
+        )
+        .is_err());
+
+    set_usdc_price(&ctx, &oracle, 90);
+    let result = ctx.broker_client.execute_trigger_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+    ctx.check_contract_fee_balance(result.get(2).unwrap());
+    ctx.check_no_interim_leftovers();
+    assert!(ctx.broker_client.try_get_trigger_order(&order_id).is_err());
+}
+
+#[test]
+fn execute_take_profit_order() {
+    let ctx = setup();
+    let oracle = setup_eurc_oracle(&ctx);
+    let keeper = Address::generate(&ctx.env);
+    set_usdc_price(&ctx, &oracle, 110);
+
+    let order_id = ctx.broker_client.place_trigger_order(&usdc_eurc_order(
+        &ctx,
+        TriggerKind::TakeProfit,
+        11_000_000,
+    ));
+    let result = ctx.broker_client.execute_trigger_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+
+    assert_eq!(result.get(0).unwrap(), amount(100));
+    ctx.check_trader_balance(&ctx.eurc, result.get(1).unwrap());
+}
+
+#[test]
+#[should_panic(expected = "32742")]
+fn execute_take_profit_order_fail_not_triggered() {
+    let ctx = setup();
+    let oracle = setup_eurc_oracle(&ctx);
+    let keeper = Address::generate(&ctx.env);
+    set_usdc_price(&ctx, &oracle, 105);
+
+    let order_id = ctx.broker_client.place_trigger_order(&usdc_eurc_order(
+        &ctx,
+        TriggerKind::TakeProfit,
+        11_000_000,
+    ));
+    ctx.broker_client.execute_trigger_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32750")]
+fn execute_trigger_order_fail_stale_price() {
+    let ctx = setup();
+    let oracle = setup_eurc_oracle(&ctx);
+    let keeper = Address::generate(&ctx.env);
+    set_usdc_price(&ctx, &oracle, 90);
+
+    let order_id = ctx.broker_client.place_trigger_order(&usdc_eurc_order(
+        &ctx,
+        TriggerKind::StopLoss,
+        9_000_000,
+    ));
+    ctx.env.ledger().set_timestamp(3600);
+    ctx.broker_client.execute_trigger_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32712")]
+fn execute_trigger_order_fail_min() {
+    let ctx = setup();
+    let oracle = setup_eurc_oracle(&ctx);
+    let keeper = Address::generate(&ctx.env);
+    set_usdc_price(&ctx, &oracle, 90);
+
+    let mut order = usdc_eurc_order(&ctx, TriggerKind::StopLoss, 9_000_000);
+    order.min = amount(90);
+    let order_id = ctx.broker_client.place_trigger_order(&order);
+    ctx.broker_client.execute_trigger_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+#[should_panic(expected = "32732")]
+fn place_trigger_order_fail_signed_only() {
+    let ctx = setup();
+    ctx.broker_client.require_signed_quotes(&true);
+    ctx.broker_client
+        .place_trigger_order(&usdc_eurc_order(&ctx, TriggerKind::StopLoss, 9_000_000));
+}
+
+#[test]
+#[should_panic(expected = "32732")]
+fn execute_trigger_order_fail_signed_only() {
+    let ctx = setup();
+    let oracle = setup_eurc_oracle(&ctx);
+    let keeper = Address::generate(&ctx.env);
+    set_usdc_price(&ctx, &oracle, 90);
+    let order_id = ctx.broker_client.place_trigger_order(&usdc_eurc_order(
+        &ctx,
+        TriggerKind::StopLoss,
+        9_000_000,
+    ));
+    //order placed before signed quotes were required
+    ctx.broker_client.require_signed_quotes(&true);
+
+    ctx.broker_client.execute_trigger_order(
+        &order_id,
+        &ctx.usdc_eurc_routes(amount(100), amount(70)),
+        &ctx.fee_path(),
+        &keeper,
+    );
+}
+
+#[test]
+fn cancel_trigger_order() {
+    let ctx = setup();
+    setup_eurc_oracle(&ctx);
+    let order_id = ctx.broker_client.place_trigger_order(&usdc_eurc_order(
+        &ctx,
+        TriggerKind::StopLoss,
+        9_000_000,
+    ));
+
+    ctx.broker_client.cancel_trigger_order(&order_id);
+    ctx.check_trader_balance(&ctx.usdc, amount(100));
+    assert_eq!(ctx.usdc_client.balance(&ctx.broker), 0);
+    assert!(ctx.broker_client.try_get_trigger_order(&order_id).is_err());
+}
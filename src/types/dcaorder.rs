@@ -0,0 +1,39 @@
+use soroban_sdk::{contracttype, Address};
+
+// Recurring (DCA) order descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct DcaOrder {
+    // Address of the trader account
+    pub trader: Address,
+    // Selling token address
+    pub selling: Address,
+    // Buying token address
+    pub buying: Address,
+    // Total escrowed selling amount
+    pub amount: i128,
+    // Selling amount per execution
+    pub slice: i128,
+    // Min interval between executions (in ledgers)
+    pub period: u32,
+    // Min price per execution (smallest buying token units per smallest selling token unit, scaled by 10^7)
+    pub price: i128,
+    // Variable fee charged from actual savings (in ‰)
+    pub vfee: u32,
+    // Fixed fee charged from total swap amount (in ‰)
+    pub ffee: u32,
+}
+
+// Recurring (DCA) order execution status
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct DcaStatus {
+    // Selling amount remaining in escrow
+    pub remaining: i128,
+    // Total amount bought by executed slices
+    pub bought: i128,
+    // Number of executed slices
+    pub executions: u32,
+    // Ledger sequence from which the next slice can be executed
+    pub next: u32,
+}
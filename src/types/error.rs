@@ -7,6 +7,10 @@ use soroban_sdk::contracterror;
 pub enum BrokerError {
     // Caller is not allowed to execute this function
     Unauthorized = 32_700,
+    // Invalid configuration parameter value
+    InvalidValue = 32_701,
+    // Requested amount exceeds unreserved contract balance
+    ReservedFunds = 32_702,
     // Protocol participating in the swap has been disabled
     ProtocolDisabled = 32_710,
     // Malformed swap route path
@@ -14,5 +18,27 @@ pub enum BrokerError {
     // Requested quote can not be executed
     Unfeasible = 32_712,
     // LP protocol charged more than projected
-    Misconduct = 32_713
+    Misconduct = 32_713,
+    // Flash swap receiver didn't deliver the selling amount
+    FlashNotRepaid = 32_714,
+    // Flash swaps have been disabled
+    FlashDisabled = 32_715,
+    // Partner referenced in the swap is not registered
+    UnknownPartner = 32_720,
+    // Signed quote has expired
+    QuoteExpired = 32_730,
+    // Signed quote nonce has been already used
+    NonceUsed = 32_731,
+    // Only quotes signed by the router service are accepted
+    SignedQuoteRequired = 32_732,
+    // Order does not exist
+    OrderNotFound = 32_740,
+    // Order has expired
+    OrderExpired = 32_741,
+    // Order can't be executed yet
+    OrderNotReady = 32_742,
+    // Oracle price is missing or outdated
+    PriceUnavailable = 32_750,
+    // Execution price deviates from the oracle price more than allowed
+    PriceDeviation = 32_751,
 }
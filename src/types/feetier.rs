@@ -0,0 +1,11 @@
+use soroban_sdk::contracttype;
+
+// Volume-based fee discount tier
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct FeeTier {
+    // Min cumulative trader volume (in fee tokens)
+    pub volume: i128,
+    // Fee discount (in basis points)
+    pub discount: u32,
+}
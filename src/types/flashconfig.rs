@@ -0,0 +1,13 @@
+use soroban_sdk::contracttype;
+
+// Flash swap settings
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct FlashConfig {
+    // Whether flash swaps are allowed
+    pub enabled: bool,
+    // Max share of the unreserved contract balance lent in a single flash swap (in basis points)
+    pub max_share: u32,
+    // Flash fee charged from the sold amount on top of the repayment (in basis points)
+    pub fee: u32,
+}
@@ -0,0 +1,17 @@
+use soroban_sdk::contracttype;
+
+use super::step::PathStep;
+
+// Route graph edge descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct GraphStep {
+    // Source node index (node 0 holds the route selling amount)
+    pub from: u32,
+    // Destination node index (the node with the highest index receives the route output)
+    pub to: u32,
+    // Share of the source node amount swapped by this step (in basis points)
+    pub share: u32,
+    // Swap step
+    pub step: PathStep,
+}
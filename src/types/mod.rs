@@ -1,5 +1,22 @@
 pub mod step;
+pub mod dcaorder;
 pub mod error;
+pub mod feetier;
+pub mod flashconfig;
+pub mod graphstep;
+pub mod oracle;
+pub mod order;
+pub mod outcome;
+pub mod payoutshare;
+pub mod poolreserves;
 pub mod protocol;
+pub mod rebalancetarget;
+pub mod registeredpool;
+pub mod relayerfee;
 pub mod route;
-pub mod swapinfo;
\ No newline at end of file
+pub mod swaphook;
+pub mod swapinfo;
+pub mod swapinput;
+pub mod swaprequest;
+pub mod swapoutput;
+pub mod triggerorder;
\ No newline at end of file
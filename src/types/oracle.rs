@@ -0,0 +1,21 @@
+use soroban_sdk::{contracttype, Address, Symbol};
+
+// SEP-40 oracle asset identifier
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub enum Asset {
+    // Stellar token contract
+    Stellar(Address),
+    // Asset outside the Stellar network
+    Other(Symbol),
+}
+
+// SEP-40 oracle price record
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct PriceData {
+    // Asset price in oracle base asset (scaled by oracle decimals)
+    pub price: i128,
+    // Price record timestamp (in seconds)
+    pub timestamp: u64,
+}
@@ -0,0 +1,25 @@
+use soroban_sdk::{contracttype, Address};
+
+// Limit order descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct LimitOrder {
+    // Address of the trader account
+    pub trader: Address,
+    // Selling token address
+    pub selling: Address,
+    // Buying token address
+    pub buying: Address,
+    // Escrowed selling amount
+    pub amount: i128,
+    // Min price (smallest buying token units per smallest selling token unit, scaled by 10^7)
+    pub price: i128,
+    // Min buying amount
+    pub min: i128,
+    // Variable fee charged from actual savings (in ‰)
+    pub vfee: u32,
+    // Fixed fee charged from total swap amount (in ‰)
+    pub ffee: u32,
+    // Ledger sequence after which the order can't be executed
+    pub expiration: u32,
+}
@@ -0,0 +1,36 @@
+use soroban_sdk::{Address, Env, Vec};
+
+// Source of selling tokens for the swap
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Funding {
+    // Transfer from the trader account
+    Transfer,
+    // Transfer from the trader account using pre-approved allowance
+    Allowance,
+    // Tokens already held by the contract in escrow
+    Escrow,
+}
+
+// Swap execution summary
+#[derive(Clone, Eq, PartialEq)]
+pub struct SwapOutcome {
+    // Buying token address
+    pub buying: Address,
+    // Sold amount
+    pub sold: i128,
+    // Bought amount minus fees, left on the contract balance for the payout
+    pub bought: i128,
+    // Received fee (in fee tokens)
+    pub fee: i128,
+    // Relayer reimbursement
+    pub relayer_fee: i128,
+    // Indexes of failed routes skipped in the best effort mode
+    pub failed: Vec<u32>,
+}
+
+impl SwapOutcome {
+    // Convert to sold/bought amounts and charged fee array
+    pub fn to_vec(&self, e: &Env) -> Vec<i128> {
+        Vec::from_array(e, [self.sold, self.bought, self.fee])
+    }
+}
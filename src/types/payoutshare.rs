@@ -0,0 +1,11 @@
+use soroban_sdk::{contracttype, Address};
+
+// Share of the bought amount transferred to the recipient
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct PayoutShare {
+    // Recipient address
+    pub recipient: Address,
+    // Share of the net bought amount (in basis points)
+    pub share: u32,
+}
@@ -0,0 +1,17 @@
+use soroban_sdk::contracttype;
+
+use super::protocol::Protocol;
+
+// Constant product pool state used to estimate swap amounts
+#[derive(Clone)]
+#[contracttype]
+pub struct PoolReserves {
+    // Protocol type
+    pub protocol: Protocol,
+    // Selling asset reserve
+    pub reserve_in: i128,
+    // Buying asset reserve
+    pub reserve_out: i128,
+    // Pool fee (in basis points)
+    pub fee: u32,
+}
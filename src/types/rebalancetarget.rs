@@ -0,0 +1,15 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+use super::step::PathStep;
+
+// Portfolio rebalance output token descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct RebalanceTarget {
+    // Buying token address
+    pub buying: Address,
+    // Min amount of buying tokens to receive after fees
+    pub min: i128,
+    // Fee conversion path for the buying token
+    pub fpath: Vec<PathStep>,
+}
@@ -0,0 +1,15 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+use super::protocol::Protocol;
+
+// Liquidity pool available for on-chain pathfinding
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct RegisteredPool {
+    // Protocol type
+    pub protocol: Protocol,
+    // LP contract address
+    pub pool: Address,
+    // Pool tokens in the pool index order
+    pub tokens: Vec<Address>,
+}
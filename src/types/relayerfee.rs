@@ -0,0 +1,11 @@
+use soroban_sdk::{contracttype, Address};
+
+// Relayer reimbursement descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct RelayerFee {
+    // Reimbursement charged from the bought amount (in buying tokens)
+    pub amount: i128,
+    // Token to pay the reimbursement in (buying or fee token)
+    pub token: Address,
+}
@@ -1,6 +1,6 @@
 use soroban_sdk::{Vec, contracttype};
 
-use super::step::PathStep;
+use super::{graphstep::GraphStep, step::PathStep};
 
 // Swap route descriptor
 #[derive(Clone, Eq, PartialEq)]
@@ -14,4 +14,10 @@ pub struct Route {
     pub min: i128,
     // Estimated buying amount
     pub estimated: i128,
+    // Alternative execution paths tried in order if the primary path fails or yields less than min,
+    // fallback happens only before the first swap, so every path except the last one should be single-step,
+    // and a Soroswap pair failing on swap reverts the transaction instead of falling back
+    pub alternatives: Vec<Vec<PathStep>>,
+    // Execution graph with split and merged amounts, replaces the path if not empty
+    pub graph: Vec<GraphStep>,
 }
@@ -0,0 +1,11 @@
+use soroban_sdk::{contracttype, Address, Bytes};
+
+// Post-swap hook descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct SwapHook {
+    // Hook contract address receiving bought tokens
+    pub contract: Address,
+    // Arbitrary payload passed to the hook contract
+    pub payload: Bytes,
+}
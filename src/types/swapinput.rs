@@ -0,0 +1,13 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+use super::route::Route;
+
+// Multi-input swap group descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct SwapInput {
+    // Selling token address
+    pub selling: Address,
+    // Chained swap routes selling the token
+    pub routes: Vec<Route>,
+}
@@ -0,0 +1,15 @@
+use soroban_sdk::{contracttype, Vec};
+
+use super::{route::Route, step::PathStep};
+
+// Multi-output swap group descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct SwapOutput {
+    // Chained swap routes ending in the same buying token
+    pub routes: Vec<Route>,
+    // Min amount of buying tokens to receive after fees
+    pub min: i128,
+    // Fee conversion path for the buying token
+    pub fpath: Vec<PathStep>,
+}
@@ -0,0 +1,23 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+use super::{route::Route, step::PathStep};
+
+// Swap request descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct SwapRequest {
+    // Selling token address
+    pub selling: Address,
+    // Chained swap routes
+    pub routes: Vec<Route>,
+    // Address of the trader account
+    pub trader: Address,
+    // Variable fee charged from actual savings (in ‰)
+    pub vfee: u32,
+    // Fixed fee charged from total swap amount (in ‰)
+    pub ffee: u32,
+    // Fee conversion path
+    pub fpath: Vec<PathStep>,
+    // Optional address of the integrator sharing the fee
+    pub partner: Option<Address>,
+}
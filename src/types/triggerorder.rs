@@ -0,0 +1,39 @@
+use soroban_sdk::{contracttype, Address};
+
+// Trigger order condition type
+#[contracttype]
+#[repr(u32)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum TriggerKind {
+    // Execute when the oracle price falls to the trigger price or below
+    StopLoss = 0,
+    // Execute when the oracle price rises to the trigger price or above
+    TakeProfit = 1,
+}
+
+// Stop-loss/take-profit order descriptor
+#[derive(Clone, Eq, PartialEq)]
+#[contracttype]
+pub struct TriggerOrder {
+    // Address of the trader account
+    pub trader: Address,
+    // Selling token address
+    pub selling: Address,
+    // Buying token address
+    pub buying: Address,
+    // Escrowed selling amount
+    pub amount: i128,
+    // Trigger condition type
+    pub kind: TriggerKind,
+    // Oracle price of the selling token in buying tokens triggering the execution
+    // (smallest buying token units per smallest selling token unit, scaled by 10^7)
+    pub trigger: i128,
+    // Min amount of buying tokens to receive
+    pub min: i128,
+    // Variable fee charged from actual savings (in ‰)
+    pub vfee: u32,
+    // Fixed fee charged from total swap amount (in ‰)
+    pub ffee: u32,
+    // Ledger sequence after which the order can't be executed
+    pub expiration: u32,
+}